@@ -0,0 +1,183 @@
+//! Batch scoring throughput of `LGBM_BoosterPredictForMat` (through `Booster::predict`),
+//! `NativeBooster` and `BatchPredictor` on the same synthetic model and inputs, with the
+//! speedup of `BatchPredictor` over the other two.
+//!
+//! Run with `cargo bench --bench native_batch --features native`.
+
+use lightgbm_rust::{predict_type, Booster, NativeBooster};
+use std::time::Instant;
+
+const NUM_FEATURES: usize = 50;
+const NUM_TREES: usize = 500;
+const NUM_LEAVES: usize = 63;
+const NUM_ROWS: usize = 200_000;
+
+/// Small deterministic generator, so runs are comparable without extra dependencies
+struct Lcg(u64);
+
+impl Lcg {
+    fn next_u32(&mut self) -> u32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as u32
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.next_u32() as usize % n
+    }
+
+    fn uniform(&mut self) -> f64 {
+        self.next_u32() as f64 / (1u64 << 31) as f64
+    }
+}
+
+/// One tree grown leaf-wise like LightGBM, splitting a random leaf each time
+fn synthetic_tree(rng: &mut Lcg) -> String {
+    let num_internal = NUM_LEAVES - 1;
+    let mut split_feature = Vec::new();
+    let mut threshold = Vec::new();
+    let mut decision_type = Vec::new();
+    let mut left_child: Vec<i32> = Vec::new();
+    let mut right_child: Vec<i32> = Vec::new();
+    // Node pointing to each leaf, and whether through its left child
+    let mut leaf_parent: Vec<Option<(usize, bool)>> = vec![None];
+
+    for node in 0..num_internal {
+        let leaf = rng.below(leaf_parent.len());
+        let new_leaf = leaf_parent.len();
+        if let Some((parent, is_left)) = leaf_parent[leaf] {
+            if is_left {
+                left_child[parent] = node as i32;
+            } else {
+                right_child[parent] = node as i32;
+            }
+        }
+        split_feature.push(rng.below(NUM_FEATURES).to_string());
+        threshold.push(format!("{:.6}", rng.uniform()));
+        // Mix of default-left/right and none/zero/NaN missing types
+        decision_type.push((rng.below(2) * 2 + rng.below(3) * 4).to_string());
+        left_child.push(!(leaf as i32));
+        right_child.push(!(new_leaf as i32));
+        leaf_parent[leaf] = Some((node, true));
+        leaf_parent.push(Some((node, false)));
+    }
+
+    let leaf_value: Vec<String> = (0..NUM_LEAVES)
+        .map(|_| format!("{:.6}", rng.uniform() - 0.5))
+        .collect();
+    let ones = |n: usize| vec!["1"; n].join(" ");
+    let join = |v: &[i32]| v.iter().map(i32::to_string).collect::<Vec<_>>().join(" ");
+    format!(
+        "num_leaves={}\nnum_cat=0\nsplit_feature={}\nsplit_gain={}\nthreshold={}\n\
+         decision_type={}\nleft_child={}\nright_child={}\nleaf_value={}\nleaf_weight={}\n\
+         leaf_count={}\ninternal_value={}\ninternal_weight={}\ninternal_count={}\n\
+         is_linear=0\nshrinkage=1\n",
+        NUM_LEAVES,
+        split_feature.join(" "),
+        ones(num_internal),
+        threshold.join(" "),
+        decision_type.join(" "),
+        join(&left_child),
+        join(&right_child),
+        leaf_value.join(" "),
+        ones(NUM_LEAVES),
+        ones(NUM_LEAVES),
+        ones(num_internal),
+        ones(num_internal),
+        ones(num_internal),
+    )
+}
+
+fn synthetic_model(rng: &mut Lcg) -> String {
+    let blocks: Vec<String> = (0..NUM_TREES)
+        .map(|i| format!("Tree={}\n{}\n\n", i, synthetic_tree(rng)))
+        .collect();
+    let sizes: Vec<String> = blocks.iter().map(|b| b.len().to_string()).collect();
+    let names: Vec<String> = (0..NUM_FEATURES).map(|i| format!("f{}", i)).collect();
+
+    format!(
+        "tree\nversion=v4\nnum_class=1\nnum_tree_per_iteration=1\nlabel_index=0\n\
+         max_feature_idx={}\nobjective=binary sigmoid:1\nfeature_names={}\n\
+         feature_infos={}\ntree_sizes={}\n\n{}end of trees\n\nfeature_importances:\n",
+        NUM_FEATURES - 1,
+        names.join(" "),
+        vec!["[0:1]"; NUM_FEATURES].join(" "),
+        sizes.join(" "),
+        blocks.concat(),
+    )
+}
+
+/// Time the fastest of a few runs, and check the output against `reference`
+fn bench(name: &str, reference: Option<&[f64]>, predict: impl Fn() -> Vec<f64>) -> (Vec<f64>, f64) {
+    // Warm up, then keep the fastest of a few runs
+    let mut output = predict();
+    let mut best = f64::INFINITY;
+    for _ in 0..3 {
+        let start = Instant::now();
+        output = predict();
+        best = best.min(start.elapsed().as_secs_f64());
+    }
+    let max_diff = reference.map_or(0.0, |reference| {
+        reference
+            .iter()
+            .zip(&output)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max)
+    });
+    println!(
+        "{:<16} {:>8.1} ms {:>12.0} rows/s   max diff {:.1e}",
+        name,
+        best * 1000.0,
+        NUM_ROWS as f64 / best,
+        max_diff
+    );
+    (output, best)
+}
+
+fn main() {
+    let mut rng = Lcg(42);
+    let text = synthetic_model(&mut rng);
+    let data: Vec<f64> = (0..NUM_ROWS * NUM_FEATURES)
+        .map(|_| {
+            if rng.below(20) == 0 {
+                f64::NAN
+            } else {
+                rng.uniform()
+            }
+        })
+        .collect();
+
+    println!(
+        "{} rows, {} features, {} trees of {} leaves",
+        NUM_ROWS, NUM_FEATURES, NUM_TREES, NUM_LEAVES
+    );
+
+    let booster = Booster::load_from_string(&text).unwrap();
+    let native = NativeBooster::load_from_string(&text).unwrap();
+    let batch = native.batch_predictor();
+    let rows = NUM_ROWS as i32;
+    let cols = NUM_FEATURES as i32;
+
+    let (reference, c_api) = bench("C API", None, || {
+        booster
+            .predict(&data, rows, cols, predict_type::NORMAL)
+            .unwrap()
+    });
+    let (_, scalar) = bench("NativeBooster", Some(&reference), || {
+        native
+            .predict(&data, rows, cols, predict_type::NORMAL)
+            .unwrap()
+    });
+    let (_, bitvector) = bench("BatchPredictor", Some(&reference), || {
+        batch
+            .predict(&data, rows, cols, predict_type::NORMAL)
+            .unwrap()
+    });
+    println!(
+        "BatchPredictor speedup: {:.2}x over NativeBooster, {:.2}x over the C API",
+        scalar / bitvector,
+        c_api / bitvector
+    );
+}
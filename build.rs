@@ -326,6 +326,11 @@ fn download_and_extract_windows_libs(
 }
 
 fn main() {
+    // Without the `ffi` feature nothing links against LightGBM, so skip the download
+    if env::var_os("CARGO_FEATURE_FFI").is_none() {
+        return;
+    }
+
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
     let lgbm_include_root = out_dir.join("include");
 
@@ -356,6 +361,7 @@ fn main() {
         .allowlist_type("ArrowSchema")
         // Allowlist constants
         .allowlist_var("C_API_DTYPE_.*")
+        .allowlist_var("C_API_FEATURE_IMPORTANCE_.*")
         // Treat Arrow types as opaque
         .opaque_type("ArrowArray")
         .opaque_type("ArrowSchema")
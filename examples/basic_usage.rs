@@ -15,6 +15,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Model loaded successfully!");
     println!("Number of features: {}", num_features);
     println!("Number of classes: {}", num_classes);
+    println!("Number of iterations: {}", booster.current_iteration()?);
+    println!("Number of trees: {}", booster.num_trees()?);
 
     // Example: Predict for a single sample with 4 features
     // Data in row-major format: [feature1, feature2, feature3, feature4]
@@ -0,0 +1,104 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::{Booster, FloatInput};
+use crate::predict_type;
+use ndarray::{Array2, Array3, ArrayView2};
+
+impl Booster {
+    /// Predict for an `ndarray` matrix with one row per sample
+    ///
+    /// Both C (row-major) and Fortran (column-major) contiguous layouts are passed to
+    /// LightGBM as-is; any other layout (e.g. a strided slice) is copied first.
+    ///
+    /// # Arguments
+    /// * `data` - Input matrix of shape `(num_rows, num_features)`, f32 or f64
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    ///
+    /// # Returns
+    /// Matrix of shape `(num_rows, outputs_per_row)`, e.g. one column per class for
+    /// multiclass models or one column per tree for leaf index prediction. With
+    /// [`CONTRIB`](crate::predict_type::CONTRIB), the contributions of all classes are laid
+    /// side by side, `num_classes * (num_features + 1)` columns.
+    ///
+    /// SHAP values are the only output with a third axis, so they have their own method,
+    /// [`predict_contrib_ndarray`](Self::predict_contrib_ndarray), returning an `Array3`. A
+    /// single method returning either shape would need an enum or a dynamically shaped
+    /// `ArrayD` that every caller has to unwrap; two methods keep the shape in the type.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use ndarray::array;
+    ///
+    /// let data = array![[1.0, 2.0, 3.0, 4.0], [2.0, 3.0, 4.0, 5.0]];
+    /// let predictions = booster.predict_ndarray(data.view(), predict_type::NORMAL)?;
+    /// ```
+    pub fn predict_ndarray<T: FloatInput + Clone>(
+        &self,
+        data: ArrayView2<'_, T>,
+        predict_type: i32,
+    ) -> LightGBMResult<Array2<f64>> {
+        let num_rows = data.nrows();
+        let predictions = self.predict_view(data, predict_type)?;
+        let per_row = predictions.len().checked_div(num_rows).unwrap_or(0);
+
+        Array2::from_shape_vec((num_rows, per_row), predictions).map_err(|e| LightGBMError {
+            description: format!("Unexpected prediction output shape: {}", e),
+        })
+    }
+
+    /// Compute SHAP feature contributions for an `ndarray` matrix
+    ///
+    /// # Arguments
+    /// * `data` - Input matrix of shape `(num_rows, num_features)`, f32 or f64
+    ///
+    /// # Returns
+    /// Array of shape `(num_rows, num_classes, num_features + 1)`; the last entry along
+    /// the final axis is the expected value (bias term).
+    pub fn predict_contrib_ndarray<T: FloatInput + Clone>(
+        &self,
+        data: ArrayView2<'_, T>,
+    ) -> LightGBMResult<Array3<f64>> {
+        let num_rows = data.nrows();
+        let num_classes = self.num_classes()?.max(1) as usize;
+        let predictions = self.predict_view(data, predict_type::CONTRIB)?;
+        let per_class = predictions
+            .len()
+            .checked_div(num_rows * num_classes)
+            .unwrap_or(0);
+
+        Array3::from_shape_vec((num_rows, num_classes, per_class), predictions).map_err(|e| {
+            LightGBMError {
+                description: format!("Unexpected contribution output shape: {}", e),
+            }
+        })
+    }
+
+    fn predict_view<T: FloatInput + Clone>(
+        &self,
+        data: ArrayView2<'_, T>,
+        predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        let (num_rows, num_cols) = data.dim();
+        let num_rows = i32::try_from(num_rows).map_err(|_| LightGBMError {
+            description: format!("Too many rows for a single prediction: {}", num_rows),
+        })?;
+        let num_cols = i32::try_from(num_cols).map_err(|_| LightGBMError {
+            description: format!("Too many columns for a single prediction: {}", num_cols),
+        })?;
+
+        if let Some(slice) = data.as_slice() {
+            return self.predict_mat(slice, num_rows, num_cols, true, predict_type);
+        }
+
+        let transposed = data.t();
+        if let Some(slice) = transposed.as_slice() {
+            return self.predict_mat(slice, num_rows, num_cols, false, predict_type);
+        }
+
+        // Neither C nor Fortran contiguous, copy into a row-major buffer
+        let owned = data.as_standard_layout();
+        let slice = owned.as_slice().ok_or_else(|| LightGBMError {
+            description: "Failed to convert input to a contiguous layout".to_string(),
+        })?;
+        self.predict_mat(slice, num_rows, num_cols, true, predict_type)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::Booster;
+use crate::sys;
+use arrow_array::ffi::to_ffi;
+use arrow_array::{Array, RecordBatch, StructArray};
+use std::ptr;
+
+impl Booster {
+    /// Predict for an Arrow `RecordBatch`
+    ///
+    /// Columns are selected and ordered by the feature names stored in the model, so the
+    /// batch may contain extra columns or list features in any order. The selected columns
+    /// are handed to LightGBM through the Arrow C Data Interface without copying; LightGBM
+    /// takes ownership of the exported structures and releases them when it is done.
+    ///
+    /// # Arguments
+    /// * `batch` - Input data, one column per feature, named as in the model
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    ///
+    /// # Returns
+    /// Vector of predictions
+    ///
+    /// # Example
+    /// ```ignore
+    /// let predictions = booster.predict_arrow(&batch, predict_type::NORMAL)?;
+    /// ```
+    pub fn predict_arrow(
+        &self,
+        batch: &RecordBatch,
+        predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        let feature_names = self.feature_names()?;
+        let schema = batch.schema();
+
+        let mut indices = Vec::with_capacity(feature_names.len());
+        let mut missing = Vec::new();
+        for name in &feature_names {
+            match schema.index_of(name) {
+                Ok(index) => indices.push(index),
+                Err(_) => missing.push(name.as_str()),
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(LightGBMError {
+                description: format!(
+                    "RecordBatch is missing model features: {}",
+                    missing.join(", ")
+                ),
+            });
+        }
+
+        let projected = batch.project(&indices).map_err(|e| LightGBMError {
+            description: format!("Failed to select feature columns: {}", e),
+        })?;
+        let num_rows = i32::try_from(projected.num_rows()).map_err(|_| LightGBMError {
+            description: format!(
+                "Too many rows for a single prediction: {}",
+                projected.num_rows()
+            ),
+        })?;
+
+        let data = StructArray::from(projected).into_data();
+        let (mut ffi_array, mut ffi_schema) = to_ffi(&data).map_err(|e| LightGBMError {
+            description: format!(
+                "Failed to export RecordBatch to Arrow C Data Interface: {}",
+                e
+            ),
+        })?;
+
+        let mut out_len = 0i64;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterCalcNumPredict(
+                self.handle,
+                num_rows,
+                predict_type,
+                0,  // start_iteration
+                -1, // num_iteration
+                &mut out_len,
+            )
+        })?;
+
+        let mut out_result = vec![0.0f64; out_len as usize];
+
+        // Ownership of the exported structures moves to LightGBM, which may call their
+        // `release` callbacks and so writes through these pointers. Released structures have
+        // a null `release`, so dropping them here afterwards only frees what LightGBM did not.
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterPredictForArrow(
+                self.handle,
+                1, // n_chunks
+                &mut ffi_array as *mut _ as *const sys::ArrowArray,
+                &mut ffi_schema as *mut _ as *const sys::ArrowSchema,
+                predict_type,
+                0,  // start_iteration
+                -1, // num_iteration
+                ptr::null(),
+                &mut out_len,
+                out_result.as_mut_ptr(),
+            )
+        })?;
+
+        Ok(out_result)
+    }
+}
@@ -0,0 +1,200 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::Booster;
+use std::thread;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{timeout_at, Instant};
+
+/// Batching settings for [`AsyncPredictor`]
+#[derive(Clone, Debug)]
+pub struct BatchConfig {
+    max_batch_size: usize,
+    max_delay: Duration,
+    predict_type: i32,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        BatchConfig {
+            max_batch_size: 64,
+            max_delay: Duration::from_millis(2),
+            predict_type: crate::predict_type::NORMAL,
+        }
+    }
+}
+
+impl BatchConfig {
+    /// Flush a batch once it holds this many rows (default 64)
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Flush a batch once its first row has waited this long (default 2ms)
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Prediction type used for every batch (default [`predict_type::NORMAL`](crate::predict_type::NORMAL))
+    pub fn with_predict_type(mut self, predict_type: i32) -> Self {
+        self.predict_type = predict_type;
+        self
+    }
+}
+
+/// Micro-batching predictor for async services
+///
+/// Single-row requests from many tasks are queued and predicted together in one
+/// `LGBM_BoosterPredictForMat` call when either the batch is full or the oldest queued row
+/// has waited `max_delay`. Each caller's future resolves with its own row of results.
+///
+/// The Booster is moved to a dedicated worker thread, so prediction never blocks the async
+/// runtime. The worker stops once the `AsyncPredictor` is dropped.
+///
+/// # Example
+/// ```ignore
+/// use lightgbm_rust::{AsyncPredictor, BatchConfig, Booster};
+/// use std::time::Duration;
+///
+/// let booster = Booster::load("model.txt")?;
+/// let config = BatchConfig::default()
+///     .with_max_batch_size(128)
+///     .with_max_delay(Duration::from_millis(1));
+/// let predictor = AsyncPredictor::new(booster, config)?;
+///
+/// let prediction = predictor.predict(&[1.0, 2.0, 3.0, 4.0]).await?;
+/// ```
+pub struct AsyncPredictor {
+    sender: mpsc::UnboundedSender<Request>,
+}
+
+struct Request {
+    row: Vec<f64>,
+    reply: oneshot::Sender<LightGBMResult<Vec<f64>>>,
+}
+
+/// The Booster owned by the worker thread
+///
+/// It is moved into the worker once and only ever used there, which is the same guarantee
+/// as creating one Booster per thread.
+struct WorkerBooster(Booster);
+
+unsafe impl Send for WorkerBooster {}
+
+impl AsyncPredictor {
+    /// Start a worker thread that predicts queued rows with `booster`
+    pub fn new(booster: Booster, config: BatchConfig) -> LightGBMResult<Self> {
+        let num_features = booster.num_features()? as usize;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .map_err(|e| LightGBMError {
+                description: format!("Failed to create batching runtime: {}", e),
+            })?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let booster = WorkerBooster(booster);
+
+        thread::Builder::new()
+            .name("lightgbm-batcher".to_string())
+            .spawn(move || {
+                // Capture the whole wrapper, not just the (non-Send) Booster field
+                let booster = booster;
+                runtime.block_on(run_batches(&booster.0, num_features, &config, receiver));
+            })
+            .map_err(|e| LightGBMError {
+                description: format!("Failed to spawn batching thread: {}", e),
+            })?;
+
+        Ok(AsyncPredictor { sender })
+    }
+
+    /// Predict a single row, batched with concurrent requests
+    ///
+    /// # Arguments
+    /// * `row` - Feature values of one sample, one per model feature
+    ///
+    /// # Returns
+    /// The predictions for this row, e.g. one value per class for multiclass models
+    pub async fn predict(&self, row: &[f64]) -> LightGBMResult<Vec<f64>> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send(Request {
+                row: row.to_vec(),
+                reply,
+            })
+            .map_err(|_| stopped())?;
+        response.await.map_err(|_| stopped())?
+    }
+}
+
+async fn run_batches(
+    booster: &Booster,
+    num_features: usize,
+    config: &BatchConfig,
+    mut receiver: mpsc::UnboundedReceiver<Request>,
+) {
+    let mut batch: Vec<Request> = Vec::with_capacity(config.max_batch_size);
+
+    while let Some(first) = receiver.recv().await {
+        let deadline = Instant::now() + config.max_delay;
+        push_checked(&mut batch, first, num_features);
+
+        while batch.len() < config.max_batch_size {
+            match timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(request)) => push_checked(&mut batch, request, num_features),
+                // Deadline reached or every sender dropped, flush what we have
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        flush(booster, num_features, config.predict_type, &mut batch);
+    }
+}
+
+/// Queue a request, rejecting rows of the wrong length right away
+fn push_checked(batch: &mut Vec<Request>, request: Request, num_features: usize) {
+    if request.row.len() == num_features {
+        batch.push(request);
+    } else {
+        let _ = request.reply.send(Err(LightGBMError {
+            description: format!(
+                "Input row size mismatch: expected {} features, got {}",
+                num_features,
+                request.row.len()
+            ),
+        }));
+    }
+}
+
+fn flush(booster: &Booster, num_features: usize, predict_type: i32, batch: &mut Vec<Request>) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let data: Vec<f64> = batch.iter().flat_map(|r| r.row.iter().copied()).collect();
+    let result = booster.predict(&data, batch.len() as i32, num_features as i32, predict_type);
+
+    match result {
+        Ok(predictions) => {
+            let per_row = predictions.len() / batch.len();
+            for (request, row) in batch.drain(..).zip(predictions.chunks(per_row.max(1))) {
+                let _ = request.reply.send(Ok(row.to_vec()));
+            }
+        }
+        Err(e) => {
+            for request in batch.drain(..) {
+                let _ = request.reply.send(Err(LightGBMError {
+                    description: e.description.clone(),
+                }));
+            }
+        }
+    }
+}
+
+fn stopped() -> LightGBMError {
+    LightGBMError {
+        description: "Batching worker has stopped".to_string(),
+    }
+}
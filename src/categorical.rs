@@ -0,0 +1,516 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::Booster;
+use std::collections::HashMap;
+
+/// Category lists saved by the LightGBM Python package for models trained on pandas DataFrames
+///
+/// Python stores the categories of each `category` dtype column in the `pandas_categorical:`
+/// trailer of the model text, in DataFrame column order. LightGBM itself only sees the integer
+/// codes, i.e. the position of a value in its column's category list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PandasCategorical {
+    features: Vec<CategoricalFeature>,
+    raw: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct CategoricalFeature {
+    index: usize,
+    categories: Vec<String>,
+    codes: HashMap<String, usize>,
+}
+
+impl PandasCategorical {
+    /// Parse the `pandas_categorical` metadata from the text of a model
+    ///
+    /// The trailer does not say which columns had the `category` dtype. They are taken from
+    /// the model's `categorical_feature` parameter, which names exactly those columns when
+    /// the Python package chose them (`categorical_feature="auto"`). Returns `None` when the
+    /// model has no categorical metadata, and logs a warning and returns `None` when the
+    /// metadata is invalid or `categorical_feature` does not name one column per category
+    /// list; use [`with_columns`](Self::with_columns) for such models.
+    pub fn from_model_str(model_str: &str) -> Option<Self> {
+        let raw = pandas_categorical_json(model_str)?;
+        let lists = match parse_category_lists(raw) {
+            Ok(lists) if lists.is_empty() => return None,
+            Ok(lists) => lists,
+            Err(e) => {
+                log::warn!("Ignoring pandas_categorical metadata: {}", e);
+                return None;
+            }
+        };
+
+        let mut feature_names: Vec<&str> = Vec::new();
+        let mut categorical_feature = "";
+        for line in model_str.lines() {
+            if let Some(names) = line.strip_prefix("feature_names=") {
+                feature_names = names.split(' ').collect();
+            } else if let Some(value) = line.strip_prefix("[categorical_feature: ") {
+                categorical_feature = value.trim_end_matches(']');
+            }
+        }
+
+        let columns = match categorical_indices(categorical_feature, &feature_names) {
+            Ok(columns) if columns.len() == lists.len() => columns,
+            Ok(columns) => {
+                log::warn!(
+                    "Ignoring pandas_categorical metadata: {} category lists but categorical_feature names {} columns, so the pandas category columns are unknown",
+                    lists.len(),
+                    columns.len()
+                );
+                return None;
+            }
+            Err(e) => {
+                log::warn!("Ignoring pandas_categorical metadata: {}", e);
+                return None;
+            }
+        };
+
+        Some(Self::new(raw, lists, columns))
+    }
+
+    /// Parse the `pandas_categorical` metadata with explicitly given category columns
+    ///
+    /// `columns` are the model column indices of the training DataFrame's `category` dtype
+    /// columns, one per category list. Returns `Ok(None)` when the model has no categorical
+    /// metadata.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Columns 1 and 4 were `category` columns, but training named other columns in
+    /// // `categorical_feature` as well
+    /// let categorical = PandasCategorical::with_columns(&model_str, &[1, 4])?;
+    /// booster.set_pandas_categorical(categorical);
+    /// ```
+    pub fn with_columns(model_str: &str, columns: &[usize]) -> LightGBMResult<Option<Self>> {
+        let raw = match pandas_categorical_json(model_str) {
+            Some(raw) => raw,
+            None => return Ok(None),
+        };
+        let lists = parse_category_lists(raw)?;
+        if lists.is_empty() {
+            return Ok(None);
+        }
+
+        let mut columns = columns.to_vec();
+        columns.sort_unstable();
+        columns.dedup();
+        if columns.len() != lists.len() {
+            return Err(LightGBMError {
+                description: format!(
+                    "Got {} category columns but pandas_categorical holds {} category lists",
+                    columns.len(),
+                    lists.len()
+                ),
+            });
+        }
+
+        Ok(Some(Self::new(raw, lists, columns)))
+    }
+
+    fn new(raw: &str, lists: Vec<Vec<String>>, columns: Vec<usize>) -> Self {
+        let features = columns
+            .into_iter()
+            .zip(lists)
+            .map(|(index, categories)| {
+                let codes = categories
+                    .iter()
+                    .enumerate()
+                    .map(|(code, category)| (category.clone(), code))
+                    .collect();
+                CategoricalFeature {
+                    index,
+                    categories,
+                    codes,
+                }
+            })
+            .collect();
+
+        PandasCategorical {
+            features,
+            raw: raw.to_string(),
+        }
+    }
+
+    /// The metadata as it appears in the model text, e.g. `[["a", "b"], [1, 2]]`
+    pub fn as_json(&self) -> &str {
+        &self.raw
+    }
+
+    /// Model column indices of the pandas `category` columns, in DataFrame column order
+    pub fn feature_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.features.iter().map(|f| f.index)
+    }
+
+    /// Category list of a feature, or `None` if the feature is not categorical
+    pub fn categories(&self, feature: usize) -> Option<&[String]> {
+        self.feature(feature).map(|f| f.categories.as_slice())
+    }
+
+    /// Integer code LightGBM expects for `value` of a categorical feature
+    ///
+    /// Returns `None` if the feature is not categorical or the value is not a known category.
+    pub fn code(&self, feature: usize, value: &str) -> Option<usize> {
+        self.feature(feature)
+            .and_then(|f| f.codes.get(value))
+            .copied()
+    }
+
+    fn feature(&self, feature: usize) -> Option<&CategoricalFeature> {
+        self.features.iter().find(|f| f.index == feature)
+    }
+}
+
+/// A single input value for [`Booster::predict_with_categories`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeatureValue<'a> {
+    /// A numeric value, or an already encoded category code
+    Number(f64),
+    /// A category label, encoded using the model's `pandas_categorical` metadata
+    Category(&'a str),
+}
+
+impl From<f64> for FeatureValue<'_> {
+    fn from(value: f64) -> Self {
+        FeatureValue::Number(value)
+    }
+}
+
+impl<'a> From<&'a str> for FeatureValue<'a> {
+    fn from(value: &'a str) -> Self {
+        FeatureValue::Category(value)
+    }
+}
+
+impl Booster {
+    /// Get the `pandas_categorical` metadata of the model, if any
+    pub fn pandas_categorical(&self) -> Option<&PandasCategorical> {
+        self.pandas_categorical.as_ref()
+    }
+
+    /// Replace the `pandas_categorical` metadata used to encode category labels
+    ///
+    /// For models whose category columns cannot be read from the model text, see
+    /// [`PandasCategorical::with_columns`]. The metadata written by
+    /// [`save_model`](Booster::save_model) is the one read at load time.
+    pub fn set_pandas_categorical(&mut self, pandas_categorical: Option<PandasCategorical>) {
+        self.pandas_categorical = pandas_categorical;
+    }
+
+    /// Predict for a dense matrix whose categorical features may be given as labels
+    ///
+    /// Category labels are mapped to the integer codes LightGBM was trained with, using the
+    /// `pandas_categorical` metadata stored in the model. Unknown categories become NaN and
+    /// are treated as missing.
+    ///
+    /// # Arguments
+    /// * `data` - Input data in row-major format (flattened 2D array)
+    /// * `num_rows` - Number of rows (samples)
+    /// * `num_cols` - Number of columns (features)
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    ///
+    /// # Returns
+    /// Vector of predictions
+    ///
+    /// # Example
+    /// ```ignore
+    /// use lightgbm_rust::FeatureValue;
+    ///
+    /// let row = [FeatureValue::Number(31.0), FeatureValue::Category("DE")];
+    /// let predictions = booster.predict_with_categories(&row, 1, 2, predict_type::NORMAL)?;
+    /// ```
+    pub fn predict_with_categories(
+        &self,
+        data: &[FeatureValue<'_>],
+        num_rows: i32,
+        num_cols: i32,
+        predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        let num_cols_usize = usize::try_from(num_cols).unwrap_or(0).max(1);
+        let encoded = data
+            .iter()
+            .enumerate()
+            .map(|(i, value)| match *value {
+                FeatureValue::Number(value) => Ok(value),
+                FeatureValue::Category(label) => {
+                    let feature = i % num_cols_usize;
+                    let categorical = self
+                        .pandas_categorical
+                        .as_ref()
+                        .filter(|c| c.categories(feature).is_some())
+                        .ok_or_else(|| LightGBMError {
+                            description: format!(
+                                "Feature {} is not a pandas categorical feature, got label '{}'",
+                                feature, label
+                            ),
+                        })?;
+                    Ok(categorical
+                        .code(feature, label)
+                        .map_or(f64::NAN, |code| code as f64))
+                }
+            })
+            .collect::<LightGBMResult<Vec<f64>>>()?;
+
+        self.predict(&encoded, num_rows, num_cols, predict_type)
+    }
+}
+
+/// Raw JSON of the `pandas_categorical:` trailer of a model text, if present
+pub(crate) fn pandas_categorical_json(model_str: &str) -> Option<&str> {
+    model_str
+        .lines()
+        .find_map(|line| line.strip_prefix("pandas_categorical:"))
+        .map(str::trim)
+}
+
+/// Resolve the `categorical_feature` parameter (indices, or `name:` prefixed names)
+fn categorical_indices(value: &str, feature_names: &[&str]) -> LightGBMResult<Vec<usize>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut indices = match value.strip_prefix("name:") {
+        Some(names) => names
+            .split(',')
+            .map(|name| {
+                feature_names
+                    .iter()
+                    .position(|n| *n == name.trim())
+                    .ok_or_else(|| LightGBMError {
+                        description: format!("Unknown categorical feature name '{}'", name),
+                    })
+            })
+            .collect::<LightGBMResult<Vec<usize>>>()?,
+        None => value
+            .split(',')
+            .map(|index| {
+                index.trim().parse::<usize>().map_err(|e| LightGBMError {
+                    description: format!("Invalid categorical feature index '{}': {}", index, e),
+                })
+            })
+            .collect::<LightGBMResult<Vec<usize>>>()?,
+    };
+
+    // Model columns follow DataFrame column order, as do the pandas_categorical lists
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+/// Parse the JSON list of category lists written by the Python package
+///
+/// Categories may be strings or numbers; numbers keep their textual form so that a label
+/// like `"3"` matches the category `3`.
+fn parse_category_lists(json: &str) -> LightGBMResult<Vec<Vec<String>>> {
+    let mut parser = JsonParser {
+        text: json.trim(),
+        pos: 0,
+    };
+
+    let mut lists = Vec::new();
+    if !parser.eat_literal("null") {
+        parser.expect(b'[')?;
+        if !parser.eat(b']') {
+            loop {
+                lists.push(parser.parse_scalar_list()?);
+                if parser.eat(b']') {
+                    break;
+                }
+                parser.expect(b',')?;
+            }
+        }
+    }
+
+    parser.skip_whitespace();
+    if parser.pos != parser.text.len() {
+        return Err(parser.error("unexpected content after the category lists"));
+    }
+    Ok(lists)
+}
+
+/// Whether a bare token is a JSON number, e.g. `-12`, `0.5` or `1e3`
+fn is_json_number(token: &str) -> bool {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+    let rest = token.strip_prefix('-').unwrap_or(token);
+    let int = digits(rest);
+    if int == 0 || (int > 1 && rest.starts_with('0')) {
+        return false;
+    }
+    let mut rest = &rest[int..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = digits(fraction);
+        if len == 0 {
+            return false;
+        }
+        rest = &fraction[len..];
+    }
+    if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        let len = digits(exponent);
+        if len == 0 {
+            return false;
+        }
+        rest = &exponent[len..];
+    }
+    rest.is_empty()
+}
+
+/// Cursor over the JSON text; `pos` is always on a character boundary
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn bytes(&self) -> &'a [u8] {
+        self.text.as_bytes()
+    }
+
+    fn error(&self, message: &str) -> LightGBMError {
+        LightGBMError {
+            description: format!(
+                "Invalid pandas_categorical metadata at offset {}: {}",
+                self.pos, message
+            ),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes()
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.bytes().get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_literal(&mut self, literal: &str) -> bool {
+        self.skip_whitespace();
+        if self.text[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> LightGBMResult<()> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn parse_scalar_list(&mut self) -> LightGBMResult<Vec<String>> {
+        let mut values = Vec::new();
+        self.expect(b'[')?;
+        if self.eat(b']') {
+            return Ok(values);
+        }
+        loop {
+            values.push(self.parse_scalar()?);
+            if self.eat(b']') {
+                return Ok(values);
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    fn parse_scalar(&mut self) -> LightGBMResult<String> {
+        self.skip_whitespace();
+        if self.eat(b'"') {
+            return self.parse_string();
+        }
+
+        let start = self.pos;
+        while self
+            .bytes()
+            .get(self.pos)
+            .is_some_and(|b| !matches!(b, b',' | b']') && !b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        // Stopped on an ASCII byte or the end, so still on a character boundary
+        let token = &self.text[start..self.pos];
+        if !(is_json_number(token) || token == "true" || token == "false") {
+            self.pos = start;
+            return Err(self.error("expected a category value"));
+        }
+        Ok(token.to_string())
+    }
+
+    fn parse_string(&mut self) -> LightGBMResult<String> {
+        let mut value = String::new();
+        loop {
+            let mut chars = self.text[self.pos..].chars();
+            let c = chars
+                .next()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += c.len_utf8();
+
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escape = chars
+                        .next()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match escape {
+                        '"' | '\\' | '/' => value.push(escape),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        'u' => value.push(self.parse_unicode_escape()?),
+                        _ => return Err(self.error("invalid escape sequence")),
+                    }
+                }
+                _ => value.push(c),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> LightGBMResult<char> {
+        let high = self.parse_hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+
+        // Surrogate pair, e.g. emoji written by json.dumps
+        if !self.text[self.pos..].starts_with("\\u") {
+            return Err(self.error("unpaired surrogate"));
+        }
+        self.pos += 2;
+        let low = self.parse_hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("unpaired surrogate"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> LightGBMResult<u32> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|d| u32::from_str_radix(d, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+}
@@ -0,0 +1,156 @@
+use crate::categorical::PandasCategorical;
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::Booster;
+use polars::prelude::*;
+
+impl Booster {
+    /// Predict for a polars `DataFrame`
+    ///
+    /// Columns are selected and reordered by the feature names stored in the model, so the
+    /// frame may contain extra columns or list features in any order. Numeric and boolean
+    /// columns are cast to f64, and nulls become NaN (treated as missing by LightGBM).
+    ///
+    /// Categorical and enum columns are encoded by label with the model's
+    /// `pandas_categorical` category lists, since polars' own codes depend on the string
+    /// cache rather than on training. Unknown categories become NaN; a categorical column
+    /// for a feature without a category list is an error.
+    ///
+    /// # Arguments
+    /// * `df` - Input data, one column per feature, named as in the model
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    ///
+    /// # Returns
+    /// A `DataFrame` with a single `prediction` column when the model produces one value
+    /// per row, otherwise one column per output named `prediction_0`, `prediction_1`, ...
+    ///
+    /// # Example
+    /// ```ignore
+    /// let predictions = booster.predict_dataframe(&df, predict_type::NORMAL)?;
+    /// let scores = predictions.column("prediction")?;
+    /// ```
+    pub fn predict_dataframe(
+        &self,
+        df: &DataFrame,
+        predict_type: i32,
+    ) -> LightGBMResult<DataFrame> {
+        let feature_names = self.feature_names()?;
+        let num_rows = df.height();
+        let num_cols = feature_names.len();
+        let num_cols_i32 = i32::try_from(num_cols).map_err(|_| LightGBMError {
+            description: format!(
+                "Too many feature columns for a single prediction: {}",
+                num_cols
+            ),
+        })?;
+
+        let missing: Vec<&str> = feature_names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| df.column(name).is_err())
+            .collect();
+        if !missing.is_empty() {
+            return Err(LightGBMError {
+                description: format!(
+                    "DataFrame is missing model features: {}",
+                    missing.join(", ")
+                ),
+            });
+        }
+
+        // Fill a row-major buffer column by column
+        let mut data = vec![f64::NAN; num_rows * num_cols];
+        for (col_idx, name) in feature_names.iter().enumerate() {
+            let values = feature_values(df, name, col_idx, self.pandas_categorical())?;
+            for (row_idx, value) in values.iter().enumerate() {
+                if let Some(value) = value {
+                    data[row_idx * num_cols + col_idx] = value;
+                }
+            }
+        }
+
+        let predictions = self.predict(
+            &data,
+            i32::try_from(num_rows).map_err(|_| LightGBMError {
+                description: format!("Too many rows for a single prediction: {}", num_rows),
+            })?,
+            num_cols_i32,
+            predict_type,
+        )?;
+        let per_row = predictions.len().checked_div(num_rows).unwrap_or(1);
+
+        let columns = if per_row == 1 {
+            vec![Column::new("prediction".into(), predictions)]
+        } else {
+            (0..per_row)
+                .map(|i| {
+                    let values: Vec<f64> = predictions
+                        .iter()
+                        .skip(i)
+                        .step_by(per_row)
+                        .copied()
+                        .collect();
+                    Column::new(format!("prediction_{}", i).into(), values)
+                })
+                .collect()
+        };
+
+        DataFrame::new(columns).map_err(polars_error)
+    }
+}
+
+/// Read a feature column as f64 values, `None` marking nulls
+fn feature_values(
+    df: &DataFrame,
+    name: &str,
+    feature: usize,
+    pandas_categorical: Option<&PandasCategorical>,
+) -> LightGBMResult<Float64Chunked> {
+    let series = df
+        .column(name)
+        .map_err(polars_error)?
+        .as_materialized_series();
+
+    let numeric = match series.dtype() {
+        DataType::Categorical(_, _) | DataType::Enum(_, _) => {
+            let categorical = pandas_categorical
+                .filter(|c| c.categories(feature).is_some())
+                .ok_or_else(|| LightGBMError {
+                    description: format!(
+                        "Feature column '{}' is categorical but the model has no pandas_categorical category list for it",
+                        name
+                    ),
+                })?;
+            let labels = series.categorical().map_err(polars_error)?;
+            return Ok(labels
+                .iter_str()
+                .map(|label| {
+                    label.map(|label| {
+                        categorical
+                            .code(feature, label)
+                            .map_or(f64::NAN, |code| code as f64)
+                    })
+                })
+                .collect());
+        }
+        dtype if dtype.is_primitive_numeric() || dtype.is_bool() => {
+            series.cast(&DataType::Float64)
+        }
+        dtype => {
+            return Err(LightGBMError {
+                description: format!(
+                    "Feature column '{}' has unsupported type {}, expected numeric, boolean or categorical",
+                    name, dtype
+                ),
+            })
+        }
+    }
+    .map_err(polars_error)?;
+
+    numeric.f64().cloned().map_err(polars_error)
+}
+
+fn polars_error(e: PolarsError) -> LightGBMError {
+    LightGBMError {
+        description: format!("Polars error: {}", e),
+    }
+}
@@ -0,0 +1,84 @@
+//! Split encoding shared by the pure-Rust evaluators
+//!
+//! LightGBM packs the kind of each split into its `decision_type`: bit 0 marks categorical
+//! splits, bit 1 sends missing values left, and bits 2-3 hold the missing type.
+
+/// LightGBM treats values with a magnitude up to this `float` constant as zero
+pub(crate) const ZERO_THRESHOLD: f32 = 1e-35;
+
+pub(crate) const CATEGORICAL_MASK: u8 = 1;
+pub(crate) const DEFAULT_LEFT_MASK: u8 = 2;
+
+pub(crate) const MISSING_TYPE_ZERO: u8 = 1;
+pub(crate) const MISSING_TYPE_NAN: u8 = 2;
+
+/// Missing type of a split: none, [`MISSING_TYPE_ZERO`] or [`MISSING_TYPE_NAN`]
+pub(crate) fn missing_type(decision_type: u8) -> u8 {
+    (decision_type >> 2) & 3
+}
+
+#[cfg(any(feature = "native", feature = "embedded"))]
+/// Input value type of an evaluator, `f64` for [`NativeBooster`](crate::NativeBooster) and
+/// `f32` for [`CompactBooster`](crate::CompactBooster)
+pub(crate) trait SplitValue: Copy + PartialOrd + core::ops::Neg<Output = Self> {
+    /// [`ZERO_THRESHOLD`] in this type
+    const ZERO_THRESHOLD: Self;
+
+    fn is_nan(self) -> bool;
+
+    /// Truncate to an integer category, saturating out-of-range values
+    fn to_category(self) -> i32;
+}
+
+#[cfg(any(feature = "native", feature = "embedded"))]
+impl SplitValue for f32 {
+    const ZERO_THRESHOLD: Self = ZERO_THRESHOLD;
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn to_category(self) -> i32 {
+        self as i32
+    }
+}
+
+#[cfg(any(feature = "native", feature = "embedded"))]
+impl SplitValue for f64 {
+    const ZERO_THRESHOLD: Self = ZERO_THRESHOLD as f64;
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn to_category(self) -> i32 {
+        self as i32
+    }
+}
+
+#[cfg(any(feature = "native", feature = "embedded"))]
+/// Whether LightGBM reads a value as zero
+#[inline]
+pub(crate) fn is_zero<T: SplitValue>(value: T) -> bool {
+    -T::ZERO_THRESHOLD <= value && value <= T::ZERO_THRESHOLD
+}
+
+#[cfg(any(feature = "native", feature = "embedded"))]
+/// As `Tree::CategoricalDecision` in LightGBM: the value is truncated to an integer
+/// category, and NaN or negative categories always go right
+#[inline]
+pub(crate) fn categorical_goes_left<T: SplitValue>(bitset: &[u32], value: T) -> bool {
+    if value.is_nan() {
+        return false;
+    }
+    // Out-of-range values saturate, so they miss the bitset like in LightGBM
+    let category = value.to_category();
+    category >= 0 && find_in_bitset(bitset, category as usize)
+}
+
+#[cfg(any(feature = "native", feature = "embedded"))]
+fn find_in_bitset(bitset: &[u32], position: usize) -> bool {
+    bitset
+        .get(position / 32)
+        .is_some_and(|word| (word >> (position % 32)) & 1 != 0)
+}
@@ -0,0 +1,280 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::{Booster, SaveOptions};
+use crate::sys;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A model as returned by LightGBM's JSON dump
+///
+/// # Example
+/// ```ignore
+/// let dump = booster.dump()?;
+/// for tree in &dump.tree_info {
+///     println!("tree {} has {} leaves", tree.tree_index, tree.num_leaves);
+///     for leaf in tree.tree_structure.leaves() {
+///         println!("  {}", leaf.leaf_value);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelDump {
+    /// Model type, always `tree`
+    pub name: String,
+    /// Model format version, e.g. `v4`
+    pub version: String,
+    /// Number of classes (1 for regression and binary classification)
+    pub num_class: usize,
+    /// Number of trees trained per boosting iteration
+    pub num_tree_per_iteration: usize,
+    /// Index of the label column in the training data
+    pub label_index: i32,
+    /// Largest feature index used by the model
+    pub max_feature_idx: i32,
+    /// Objective with its parameters, e.g. `binary sigmoid:1`
+    #[serde(default)]
+    pub objective: Option<String>,
+    /// Whether the output is averaged over trees (random forest mode)
+    #[serde(default)]
+    pub average_output: bool,
+    /// Feature names, in training column order
+    pub feature_names: Vec<String>,
+    /// Monotone constraint per feature, empty if none were used
+    #[serde(default)]
+    pub monotone_constraints: Vec<i32>,
+    /// Value ranges (numerical) or category lists (categorical), keyed by feature name
+    #[serde(default)]
+    pub feature_infos: HashMap<String, FeatureInfo>,
+    /// The trees of the ensemble, in training order
+    pub tree_info: Vec<Tree>,
+    /// Feature importance, keyed by feature name (features never used are omitted)
+    #[serde(default)]
+    pub feature_importances: HashMap<String, f64>,
+}
+
+/// Value range of a feature seen during training
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeatureInfo {
+    /// Smallest value (numerical features)
+    pub min_value: f64,
+    /// Largest value (numerical features)
+    pub max_value: f64,
+    /// Categories (categorical features)
+    #[serde(default)]
+    pub values: Vec<i64>,
+}
+
+/// A single tree of the ensemble
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tree {
+    /// Position of the tree in the ensemble
+    pub tree_index: usize,
+    /// Number of leaves
+    pub num_leaves: usize,
+    /// Number of categorical splits
+    pub num_cat: usize,
+    /// Learning rate applied to the leaf values
+    pub shrinkage: f64,
+    /// Root node
+    pub tree_structure: Node,
+}
+
+/// A node of a tree, either a split or a leaf
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Node {
+    /// An internal node
+    Split(SplitNode),
+    /// A leaf
+    Leaf(LeafNode),
+}
+
+impl Node {
+    /// Whether this node is a leaf
+    pub fn is_leaf(&self) -> bool {
+        matches!(self, Node::Leaf(_))
+    }
+
+    /// All leaves below this node, from left to right
+    pub fn leaves(&self) -> Vec<&LeafNode> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            match node {
+                Node::Split(split) => {
+                    stack.push(&split.right_child);
+                    stack.push(&split.left_child);
+                }
+                Node::Leaf(leaf) => leaves.push(leaf),
+            }
+        }
+        leaves
+    }
+
+    /// Number of edges on the longest path from this node to a leaf
+    pub fn depth(&self) -> usize {
+        match self {
+            Node::Split(split) => 1 + split.left_child.depth().max(split.right_child.depth()),
+            Node::Leaf(_) => 0,
+        }
+    }
+}
+
+/// An internal node of a tree
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SplitNode {
+    /// Index of the split within its tree
+    pub split_index: usize,
+    /// Feature the split tests
+    pub split_feature: usize,
+    /// Gain of the split
+    pub split_gain: f64,
+    /// Threshold (numerical) or categories going left (categorical)
+    pub threshold: Threshold,
+    /// Comparison performed by the split
+    pub decision_type: DecisionType,
+    /// Whether missing values go to the left child
+    pub default_left: bool,
+    /// Which values are treated as missing
+    pub missing_type: MissingType,
+    /// Output of the node if it were a leaf
+    pub internal_value: f64,
+    /// Sum of hessians of the training samples reaching the node
+    #[serde(default)]
+    pub internal_weight: f64,
+    /// Number of training samples reaching the node
+    pub internal_count: u64,
+    /// Child taken when the comparison is true
+    pub left_child: Box<Node>,
+    /// Child taken otherwise
+    pub right_child: Box<Node>,
+}
+
+/// A leaf of a tree
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LeafNode {
+    /// Index of the leaf within its tree (absent for single-leaf trees)
+    #[serde(default)]
+    pub leaf_index: usize,
+    /// Output value
+    pub leaf_value: f64,
+    /// Sum of hessians of the training samples reaching the leaf
+    #[serde(default)]
+    pub leaf_weight: f64,
+    /// Number of training samples reaching the leaf
+    #[serde(default)]
+    pub leaf_count: u64,
+    /// Constant term of a linear leaf
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_const: Option<f64>,
+    /// Features of a linear leaf
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_features: Option<Vec<usize>>,
+    /// Coefficients of a linear leaf, one per entry of `leaf_features`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_coeff: Option<Vec<f64>>,
+}
+
+/// Split threshold
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawThreshold", into = "RawThreshold")]
+pub enum Threshold {
+    /// Values `<=` the threshold go left
+    Numerical(f64),
+    /// Values in the category list go left
+    Categorical(Vec<i32>),
+}
+
+/// Threshold as written by LightGBM: a number, or categories joined by `||`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawThreshold {
+    Number(f64),
+    Text(String),
+}
+
+impl TryFrom<RawThreshold> for Threshold {
+    type Error = String;
+
+    fn try_from(raw: RawThreshold) -> Result<Self, Self::Error> {
+        match raw {
+            RawThreshold::Number(value) => Ok(Threshold::Numerical(value)),
+            RawThreshold::Text(text) => text
+                .split("||")
+                .map(|c| {
+                    c.parse::<i32>()
+                        .map_err(|e| format!("invalid category '{}': {}", c, e))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Threshold::Categorical),
+        }
+    }
+}
+
+impl From<Threshold> for RawThreshold {
+    fn from(threshold: Threshold) -> Self {
+        match threshold {
+            Threshold::Numerical(value) => RawThreshold::Number(value),
+            Threshold::Categorical(categories) => RawThreshold::Text(
+                categories
+                    .iter()
+                    .map(i32::to_string)
+                    .collect::<Vec<_>>()
+                    .join("||"),
+            ),
+        }
+    }
+}
+
+/// Comparison performed by a split
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DecisionType {
+    /// Numerical split, `value <= threshold` goes left
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    /// Categorical split, `value in categories` goes left
+    #[serde(rename = "==")]
+    Equal,
+}
+
+/// Which values a split treats as missing
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MissingType {
+    /// No missing values were seen in training, NaN is converted to zero
+    None,
+    /// Zero (and NaN) are missing
+    Zero,
+    /// NaN is missing
+    NaN,
+}
+
+impl Booster {
+    /// Dump the model structure as typed trees
+    ///
+    /// Wraps `LGBM_BoosterDumpModel` and deserializes its JSON output.
+    pub fn dump(&self) -> LightGBMResult<ModelDump> {
+        self.dump_with_options(&SaveOptions::default())
+    }
+
+    /// Dump a range of iterations, with the given feature importance type
+    pub fn dump_with_options(&self, options: &SaveOptions) -> LightGBMResult<ModelDump> {
+        let json = self.dump_json(options)?;
+        serde_json::from_str(&json).map_err(|e| LightGBMError {
+            description: format!("Failed to parse model dump: {}", e),
+        })
+    }
+
+    /// Dump the model as a JSON string, as returned by LightGBM
+    pub fn dump_json(&self, options: &SaveOptions) -> LightGBMResult<String> {
+        self.read_string(|buffer_len, out_len, out_str| unsafe {
+            sys::LGBM_BoosterDumpModel(
+                self.handle,
+                options.start_iteration,
+                options.num_iteration,
+                options.importance_type.to_c_api(),
+                buffer_len,
+                out_len,
+                out_str,
+            )
+        })
+    }
+}
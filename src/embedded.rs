@@ -0,0 +1,383 @@
+use crate::decision_type::{
+    categorical_goes_left, is_zero, missing_type, CATEGORICAL_MASK, DEFAULT_LEFT_MASK,
+    MISSING_TYPE_NAN, MISSING_TYPE_ZERO,
+};
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::objective::Objective;
+use alloc::format;
+use alloc::vec::Vec;
+
+/// First bytes of a compact model
+pub(crate) const MAGIC: &[u8; 4] = b"LGBC";
+pub(crate) const VERSION: u32 = 1;
+
+/// A model in the compact binary format, evaluated without `std`
+///
+/// The format is produced on a host from a text model with
+/// `NativeBooster::to_compact` (features `native` and `embedded`), so no text parsing happens
+/// on the device. Loading allocates the model's node arrays once; scoring a row only reads them
+/// and writes into a caller-provided output slice, so prediction never allocates.
+///
+/// Rows are `f32`, like LightGBM's `float` input: thresholds are stored as the largest `f32`
+/// not above the model's `double` threshold, which sends every `f32` value the same way as
+/// the original split. Leaf values and scores stay `f64`, so outputs match
+/// `NativeBooster::predict` on the same `f32` rows. Linear trees are not supported.
+///
+/// # Example
+/// ```no_run
+/// use lightgbm_rust::CompactBooster;
+///
+/// // e.g. `include_bytes!("model.lgbc")`, kept in flash
+/// fn score(model: &[u8], row: &[f32]) -> f64 {
+///     let booster = CompactBooster::from_bytes(model).unwrap();
+///     let mut output = [0.0f64; 1];
+///     booster.predict(row, &mut output).unwrap();
+///     output[0]
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CompactBooster {
+    trees: Vec<CompactTree>,
+    nodes: Vec<CompactNode>,
+    leaf_value: Vec<f64>,
+    /// Offsets of each categorical split's bitset in `cat_threshold`
+    cat_boundaries: Vec<u32>,
+    cat_threshold: Vec<u32>,
+    objective: Objective,
+    num_features: usize,
+    num_tree_per_iteration: usize,
+    average_output: bool,
+}
+
+/// Position of a tree's nodes and leaves in the shared arrays
+#[derive(Clone, Copy, Debug)]
+struct CompactTree {
+    first_node: usize,
+    first_leaf: usize,
+    num_leaves: usize,
+}
+
+/// An internal node; children are tree-local, with leaves encoded as `!leaf` like in LightGBM
+#[derive(Clone, Copy, Debug)]
+struct CompactNode {
+    feature: u32,
+    /// Split threshold, or the index of the bitset for a categorical split
+    threshold: f32,
+    left: i32,
+    right: i32,
+    decision_type: u8,
+}
+
+impl CompactBooster {
+    /// Load a model from the compact binary format
+    pub fn from_bytes(bytes: &[u8]) -> LightGBMResult<Self> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(error("not a compact model"));
+        }
+        let version = reader.u32()?;
+        if version != VERSION {
+            return Err(LightGBMError {
+                description: format!("Unsupported compact model version {}", version),
+            });
+        }
+
+        let num_features = reader.u32()? as usize;
+        let num_tree_per_iteration = reader.u32()? as usize;
+        let average_output = reader.u8()? != 0;
+        let objective = Objective::from_tag(reader.u8()?, reader.f64()?)
+            .ok_or_else(|| error("unknown objective"))?;
+        let num_trees = reader.u32()? as usize;
+        if num_tree_per_iteration == 0 || num_trees % num_tree_per_iteration != 0 {
+            return Err(LightGBMError {
+                description: format!(
+                    "Compact model has {} trees, not a multiple of {} trees per iteration",
+                    num_trees, num_tree_per_iteration
+                ),
+            });
+        }
+
+        let num_bitsets = reader.u32()? as usize;
+        let num_boundaries = num_bitsets
+            .checked_add(1)
+            .ok_or_else(|| error("too many categorical bitsets"))?;
+        let cat_boundaries = reader.u32_vec(num_boundaries)?;
+        if cat_boundaries.first() != Some(&0) || cat_boundaries.windows(2).any(|w| w[0] > w[1]) {
+            return Err(error("invalid categorical bitset boundaries"));
+        }
+        let num_words = cat_boundaries.get(num_bitsets).copied().unwrap_or(0);
+        let cat_threshold = reader.u32_vec(num_words as usize)?;
+
+        // Every tree takes at least 4 bytes, which bounds a corrupt count
+        let mut trees = Vec::with_capacity(num_trees.min(reader.bytes.len() / 4));
+        let mut nodes = Vec::new();
+        let mut leaf_value = Vec::new();
+        for t in 0..num_trees {
+            let num_leaves = reader.u32()? as usize;
+            if num_leaves == 0 {
+                return Err(LightGBMError {
+                    description: format!("Tree {} of compact model has no leaves", t),
+                });
+            }
+            let first_node = nodes.len();
+            for node in 0..num_leaves - 1 {
+                let node = CompactNode::read(&mut reader, node, num_leaves, num_bitsets)
+                    .ok_or_else(|| LightGBMError {
+                        description: format!("Tree {}: invalid node {}", t, node),
+                    })?;
+                if node.feature as usize >= num_features {
+                    return Err(LightGBMError {
+                        description: format!(
+                            "Tree {} splits on feature {} of {}",
+                            t, node.feature, num_features
+                        ),
+                    });
+                }
+                nodes.push(node);
+            }
+            let first_leaf = leaf_value.len();
+            for _ in 0..num_leaves {
+                leaf_value.push(reader.f64()?);
+            }
+            trees.push(CompactTree {
+                first_node,
+                first_leaf,
+                num_leaves,
+            });
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(error("trailing bytes after the last tree"));
+        }
+
+        Ok(CompactBooster {
+            trees,
+            nodes,
+            leaf_value,
+            cat_boundaries,
+            cat_threshold,
+            objective,
+            num_features,
+            num_tree_per_iteration,
+            average_output,
+        })
+    }
+
+    /// Get the number of features the model expects
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Get the number of outputs per row (the number of classes for multiclass models)
+    pub fn num_outputs(&self) -> usize {
+        self.num_tree_per_iteration
+    }
+
+    /// Get the number of trees in the model
+    pub fn num_trees(&self) -> usize {
+        self.trees.len()
+    }
+
+    /// Predict one row, writing [`num_outputs`](Self::num_outputs) transformed scores
+    pub fn predict(&self, row: &[f32], output: &mut [f64]) -> LightGBMResult<()> {
+        self.predict_raw(row, output)?;
+        if self.average_output {
+            let num_iterations = (self.trees.len() / self.num_tree_per_iteration).max(1);
+            for score in output.iter_mut() {
+                *score /= num_iterations as f64;
+            }
+        }
+        self.objective.transform(output);
+        Ok(())
+    }
+
+    /// Predict one row, writing [`num_outputs`](Self::num_outputs) raw scores
+    pub fn predict_raw(&self, row: &[f32], output: &mut [f64]) -> LightGBMResult<()> {
+        if row.len() != self.num_features {
+            return Err(LightGBMError {
+                description: format!(
+                    "Row has {} values but the model expects {} features",
+                    row.len(),
+                    self.num_features
+                ),
+            });
+        }
+        if output.len() != self.num_tree_per_iteration {
+            return Err(LightGBMError {
+                description: format!(
+                    "Output has {} values but the model produces {} per row",
+                    output.len(),
+                    self.num_tree_per_iteration
+                ),
+            });
+        }
+
+        output.fill(0.0);
+        for iteration in self.trees.chunks_exact(self.num_tree_per_iteration) {
+            for (score, tree) in output.iter_mut().zip(iteration) {
+                *score += self.leaf_value[tree.first_leaf + self.leaf_index(tree, row)];
+            }
+        }
+        Ok(())
+    }
+
+    fn leaf_index(&self, tree: &CompactTree, row: &[f32]) -> usize {
+        if tree.num_leaves <= 1 {
+            return 0;
+        }
+        let mut node = 0i32;
+        while node >= 0 {
+            let split = &self.nodes[tree.first_node + node as usize];
+            node = if self.goes_left(split, row[split.feature as usize]) {
+                split.left
+            } else {
+                split.right
+            };
+        }
+        !node as usize
+    }
+
+    fn goes_left(&self, node: &CompactNode, value: f32) -> bool {
+        // Like the C API, which drops values LightGBM considers zero when reading rows
+        let value = if is_zero(value) { 0.0 } else { value };
+        if node.decision_type & CATEGORICAL_MASK != 0 {
+            let bitset = node.threshold as usize;
+            let start = self.cat_boundaries[bitset] as usize;
+            let end = self.cat_boundaries[bitset + 1] as usize;
+            return categorical_goes_left(&self.cat_threshold[start..end], value);
+        }
+
+        let missing_type = missing_type(node.decision_type);
+        let value = if value.is_nan() && missing_type != MISSING_TYPE_NAN {
+            0.0
+        } else {
+            value
+        };
+        let is_missing = match missing_type {
+            MISSING_TYPE_ZERO => is_zero(value),
+            MISSING_TYPE_NAN => value.is_nan(),
+            _ => false,
+        };
+        if is_missing {
+            node.decision_type & DEFAULT_LEFT_MASK != 0
+        } else {
+            value <= node.threshold
+        }
+    }
+}
+
+impl CompactNode {
+    /// Read node `index` of a tree, checking that its children lead to leaves
+    fn read(
+        reader: &mut Reader<'_>,
+        index: usize,
+        num_leaves: usize,
+        num_bitsets: usize,
+    ) -> Option<Self> {
+        let node = CompactNode {
+            feature: reader.u32().ok()?,
+            decision_type: reader.u8().ok()?,
+            threshold: reader.f32().ok()?,
+            left: reader.i32().ok()?,
+            right: reader.i32().ok()?,
+        };
+        // Children after their parent guarantee that traversal terminates
+        let valid_child = |child: i32| {
+            if child >= 0 {
+                (child as usize) > index && (child as usize) < num_leaves - 1
+            } else {
+                (!child as usize) < num_leaves
+            }
+        };
+        let valid_split = node.decision_type & CATEGORICAL_MASK == 0
+            || (node.threshold >= 0.0 && (node.threshold as usize) < num_bitsets);
+        (valid_child(node.left) && valid_child(node.right) && valid_split).then_some(node)
+    }
+}
+
+impl Objective {
+    /// Tag and parameter identifying the transform in a compact model
+    #[cfg(feature = "native")]
+    pub(crate) fn to_tag(self) -> (u8, f64) {
+        match self {
+            Objective::Identity => (0, 0.0),
+            Objective::Square => (1, 0.0),
+            Objective::Exp => (2, 0.0),
+            Objective::Sigmoid(sigmoid) => (3, sigmoid),
+            Objective::Softmax => (4, 0.0),
+            Objective::SigmoidPerClass(sigmoid) => (5, sigmoid),
+            Objective::CrossEntropy => (6, 0.0),
+            Objective::CrossEntropyLambda => (7, 0.0),
+        }
+    }
+
+    fn from_tag(tag: u8, param: f64) -> Option<Self> {
+        Some(match tag {
+            0 => Objective::Identity,
+            1 => Objective::Square,
+            2 => Objective::Exp,
+            3 => Objective::Sigmoid(param),
+            4 => Objective::Softmax,
+            5 => Objective::SigmoidPerClass(param),
+            6 => Objective::CrossEntropy,
+            7 => Objective::CrossEntropyLambda,
+            _ => return None,
+        })
+    }
+}
+
+/// Little-endian reader over the bytes of a compact model
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> LightGBMResult<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(error("compact model is truncated"));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> LightGBMResult<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> LightGBMResult<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> LightGBMResult<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> LightGBMResult<i32> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> LightGBMResult<f32> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> LightGBMResult<f64> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn u32_vec(&mut self, len: usize) -> LightGBMResult<Vec<u32>> {
+        // Check the length first, so a corrupt count cannot trigger a huge allocation
+        if self.bytes.len() / 4 < len {
+            return Err(error("compact model is truncated"));
+        }
+        (0..len).map(|_| self.u32()).collect()
+    }
+}
+
+fn error(message: &str) -> LightGBMError {
+    LightGBMError {
+        description: format!("Invalid compact model: {}", message),
+    }
+}
@@ -1,14 +1,19 @@
+#[cfg(feature = "ffi")]
 use crate::sys;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "ffi")]
 use std::ffi::CStr;
-use std::fmt;
 
-pub type LightGBMResult<T> = std::result::Result<T, LightGBMError>;
+pub type LightGBMResult<T> = core::result::Result<T, LightGBMError>;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct LightGBMError {
     pub description: String,
 }
 
+#[cfg(feature = "ffi")]
 impl LightGBMError {
     /// Check the return value from a LightGBM FFI call, and return the last error message on error.
     /// Return values of 0 are treated as success, non-zero values are treated as errors.
@@ -36,4 +41,5 @@ impl fmt::Display for LightGBMError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for LightGBMError {}
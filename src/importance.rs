@@ -0,0 +1,104 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::{Booster, ImportanceType};
+use crate::sys;
+
+/// Feature importance of a model, one value per feature in model column order
+///
+/// # Example
+/// ```ignore
+/// let importance = booster.feature_importance(ImportanceType::Gain, -1)?;
+/// for (name, value) in importance.normalized().sorted() {
+///     println!("{:>20}: {:.3}", name, value);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeatureImportance {
+    importance_type: ImportanceType,
+    names: Vec<String>,
+    values: Vec<f64>,
+}
+
+impl FeatureImportance {
+    /// Kind of importance held (split counts or total gain)
+    pub fn importance_type(&self) -> ImportanceType {
+        self.importance_type
+    }
+
+    /// Importance of a feature by name
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| self.values[i])
+    }
+
+    /// Importance values in model column order
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// `(name, importance)` pairs in model column order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> + '_ {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.values.iter().copied())
+    }
+
+    /// `(name, importance)` pairs, most important first
+    ///
+    /// Features with equal importance keep their model column order.
+    pub fn sorted(&self) -> Vec<(&str, f64)> {
+        let mut pairs: Vec<(&str, f64)> = self.iter().collect();
+        pairs.sort_by(|a, b| b.1.total_cmp(&a.1));
+        pairs
+    }
+
+    /// Importance scaled so that all values sum to 1
+    ///
+    /// A model without splits (all values zero) is returned unchanged.
+    pub fn normalized(&self) -> FeatureImportance {
+        let total: f64 = self.values.iter().sum();
+        let values = if total > 0.0 {
+            self.values.iter().map(|v| v / total).collect()
+        } else {
+            self.values.clone()
+        };
+        FeatureImportance {
+            importance_type: self.importance_type,
+            names: self.names.clone(),
+            values,
+        }
+    }
+}
+
+impl Booster {
+    /// Get the feature importance of the model
+    ///
+    /// # Arguments
+    /// * `importance_type` - Number of splits using a feature, or their total gain
+    /// * `num_iteration` - Only count the first `num_iteration` iterations, `<= 0` meaning all
+    pub fn feature_importance(
+        &self,
+        importance_type: ImportanceType,
+        num_iteration: i32,
+    ) -> LightGBMResult<FeatureImportance> {
+        let names = self.feature_names()?;
+        let mut values = vec![0.0f64; names.len()];
+
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterFeatureImportance(
+                self.handle,
+                num_iteration,
+                importance_type.to_c_api(),
+                values.as_mut_ptr(),
+            )
+        })?;
+
+        Ok(FeatureImportance {
+            importance_type,
+            names,
+            values,
+        })
+    }
+}
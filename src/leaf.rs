@@ -0,0 +1,90 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::Booster;
+use crate::sys;
+
+impl Booster {
+    /// Get the output value of a leaf
+    ///
+    /// # Arguments
+    /// * `tree` - Index of the tree, in `0..num_trees()`
+    /// * `leaf` - Index of the leaf within the tree
+    pub fn get_leaf_value(&self, tree: i32, leaf: i32) -> LightGBMResult<f64> {
+        self.check_leaf_index(tree, leaf)?;
+
+        let mut value = 0.0f64;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterGetLeafValue(self.handle, tree, leaf, &mut value)
+        })?;
+        Ok(value)
+    }
+
+    /// Set the output value of a leaf
+    ///
+    /// The edited model can be written out with [`save_model`](Self::save_model) or
+    /// [`save_model_to_string`](Self::save_model_to_string).
+    ///
+    /// # Arguments
+    /// * `tree` - Index of the tree, in `0..num_trees()`
+    /// * `leaf` - Index of the leaf within the tree
+    /// * `value` - New output value
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::{Booster, SaveOptions};
+    ///
+    /// let mut booster = Booster::load("model.txt").unwrap();
+    /// // Halve the contribution of the first leaf of tree 3
+    /// let value = booster.get_leaf_value(3, 0).unwrap();
+    /// booster.set_leaf_value(3, 0, value * 0.5).unwrap();
+    /// booster.save_model("edited.txt", &SaveOptions::default()).unwrap();
+    /// ```
+    pub fn set_leaf_value(&mut self, tree: i32, leaf: i32, value: f64) -> LightGBMResult<()> {
+        self.check_leaf_index(tree, leaf)?;
+
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterSetLeafValue(self.handle, tree, leaf, value)
+        })
+    }
+
+    /// Get the number of leaves of a tree
+    pub fn num_leaves(&self, tree: i32) -> LightGBMResult<i32> {
+        let counts = &self.leaf_counts;
+        usize::try_from(tree)
+            .ok()
+            .and_then(|t| counts.get(t))
+            .copied()
+            .ok_or_else(|| LightGBMError {
+                description: format!(
+                    "Tree index {} out of range, model has {} trees",
+                    tree,
+                    counts.len()
+                ),
+            })
+    }
+
+    /// LightGBM does not bounds check leaf access, so validate indices before every call
+    fn check_leaf_index(&self, tree: i32, leaf: i32) -> LightGBMResult<()> {
+        let num_leaves = self.num_leaves(tree)?;
+        if leaf < 0 || leaf >= num_leaves {
+            return Err(LightGBMError {
+                description: format!(
+                    "Leaf index {} out of range, tree {} has {} leaves",
+                    leaf, tree, num_leaves
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Number of leaves of each tree, read from the `num_leaves=` lines of a model text
+///
+/// Called on the text LightGBM has just loaded, so every line parses. Leaf values can change
+/// but the tree structure cannot, so the counts never go stale.
+pub(crate) fn leaf_counts(model_str: &str) -> Vec<i32> {
+    model_str
+        .lines()
+        .filter_map(|line| line.strip_prefix("num_leaves="))
+        .filter_map(|n| n.trim().parse().ok())
+        .collect()
+}
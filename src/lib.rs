@@ -1,11 +1,99 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 // Include the LightGBM C API bindings
+#[cfg(feature = "ffi")]
 mod sys;
 
 mod error;
 pub use crate::error::{LightGBMError, LightGBMResult};
 
+#[cfg(feature = "ffi")]
 mod model;
-pub use crate::model::Booster;
+#[cfg(feature = "ffi")]
+pub use crate::model::{Booster, FeatureNameDiff, ImportanceType, SaveOptions};
+
+#[cfg(feature = "ffi")]
+mod named;
+#[cfg(feature = "ffi")]
+pub use crate::named::UnknownFeatures;
+
+#[cfg(feature = "ffi")]
+mod categorical;
+#[cfg(feature = "ffi")]
+pub use crate::categorical::{FeatureValue, PandasCategorical};
+
+#[cfg(feature = "ffi")]
+mod importance;
+#[cfg(feature = "ffi")]
+pub use crate::importance::FeatureImportance;
+
+#[cfg(feature = "ffi")]
+mod leaf;
+
+#[cfg(feature = "std")]
+pub mod model_format;
+
+#[cfg(feature = "ffi")]
+mod stream;
+#[cfg(feature = "ffi")]
+pub use crate::stream::PredictIter;
+
+#[cfg(feature = "ffi")]
+mod policy;
+#[cfg(feature = "ffi")]
+pub use crate::policy::{AdjustedValue, Adjustment, InfinityPolicy, InputPolicy, InputReport};
+
+#[cfg(feature = "serde")]
+mod dump;
+#[cfg(feature = "serde")]
+pub use crate::dump::{
+    DecisionType, FeatureInfo, LeafNode, MissingType, ModelDump, Node, SplitNode, Threshold, Tree,
+};
+
+#[cfg(feature = "serde")]
+mod params;
+#[cfg(feature = "serde")]
+pub use crate::params::ModelParams;
+
+#[cfg(any(feature = "std", feature = "embedded"))]
+pub mod objective;
+
+#[cfg(any(feature = "native", feature = "embedded", feature = "onnx"))]
+mod decision_type;
+
+#[cfg(feature = "native")]
+mod native;
+#[cfg(feature = "native")]
+pub use crate::native::{BatchPredictor, NativeBooster};
+
+#[cfg(feature = "embedded")]
+mod embedded;
+#[cfg(feature = "embedded")]
+pub use crate::embedded::CompactBooster;
+
+#[cfg(feature = "onnx")]
+mod onnx;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(feature = "ndarray")]
+mod array;
+
+#[cfg(feature = "polars")]
+mod dataframe;
+
+#[cfg(feature = "rayon")]
+mod pool;
+#[cfg(feature = "rayon")]
+pub use crate::pool::BoosterPool;
+
+#[cfg(feature = "tokio")]
+mod async_predictor;
+#[cfg(feature = "tokio")]
+pub use crate::async_predictor::{AsyncPredictor, BatchConfig};
 
 // Re-export prediction type constants for convenience
 pub mod predict_type {
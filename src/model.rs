@@ -1,6 +1,10 @@
+use crate::categorical::{pandas_categorical_json, PandasCategorical};
 use crate::error::{LightGBMError, LightGBMResult};
+use crate::leaf::leaf_counts;
 use crate::sys;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::io::Write;
 use std::path::Path;
 use std::ptr;
 
@@ -31,6 +35,114 @@ impl FloatInput for f64 {
     }
 }
 
+/// Kind of feature importance: split counts or total gain
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ImportanceType {
+    /// Number of times a feature is used in a split
+    #[default]
+    Split,
+    /// Total gain of the splits that use a feature
+    Gain,
+}
+
+impl ImportanceType {
+    pub(crate) fn to_c_api(self) -> i32 {
+        match self {
+            ImportanceType::Split => sys::C_API_FEATURE_IMPORTANCE_SPLIT as i32,
+            ImportanceType::Gain => sys::C_API_FEATURE_IMPORTANCE_GAIN as i32,
+        }
+    }
+}
+
+/// Options for [`Booster::save_model`] and [`Booster::save_model_to_string`]
+///
+/// # Example
+/// ```ignore
+/// // Save only the first 100 iterations, with gain importance
+/// let options = SaveOptions::default()
+///     .with_num_iteration(100)
+///     .with_importance_type(ImportanceType::Gain);
+/// booster.save_model("model_100.txt", &options)?;
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SaveOptions {
+    pub(crate) start_iteration: i32,
+    pub(crate) num_iteration: i32,
+    pub(crate) importance_type: ImportanceType,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        SaveOptions {
+            start_iteration: 0,
+            num_iteration: -1,
+            importance_type: ImportanceType::Split,
+        }
+    }
+}
+
+impl SaveOptions {
+    /// First iteration to save (default 0)
+    pub fn with_start_iteration(mut self, start_iteration: i32) -> Self {
+        self.start_iteration = start_iteration;
+        self
+    }
+
+    /// Number of iterations to save, `-1` meaning all (default -1)
+    pub fn with_num_iteration(mut self, num_iteration: i32) -> Self {
+        self.num_iteration = num_iteration;
+        self
+    }
+
+    /// Feature importance written to the model (default [`ImportanceType::Split`])
+    pub fn with_importance_type(mut self, importance_type: ImportanceType) -> Self {
+        self.importance_type = importance_type;
+        self
+    }
+}
+
+/// Difference between a set of input column names and the model's feature names
+///
+/// Returned by [`Booster::validate_feature_names`]; all lists are empty when the names
+/// match exactly.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FeatureNameDiff {
+    /// Model features absent from the input
+    pub missing: Vec<String>,
+    /// Input names that are not model features
+    pub extra: Vec<String>,
+    /// Names present in both but at a different position, with (model, input) positions
+    pub misordered: Vec<(String, usize, usize)>,
+}
+
+impl FeatureNameDiff {
+    /// Whether the input names match the model's feature names exactly, in order
+    pub fn is_match(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.misordered.is_empty()
+    }
+
+    fn between(model_names: &[String], input_names: &[&str]) -> Self {
+        let mut diff = FeatureNameDiff::default();
+
+        for (model_pos, name) in model_names.iter().enumerate() {
+            match input_names.iter().position(|n| n == name) {
+                Some(input_pos) if input_pos != model_pos => {
+                    diff.misordered.push((name.clone(), model_pos, input_pos))
+                }
+                Some(_) => {}
+                None => diff.missing.push(name.clone()),
+            }
+        }
+        diff.extra = input_names
+            .iter()
+            .filter(|n| !model_names.iter().any(|m| m == *n))
+            .map(|n| n.to_string())
+            .collect();
+
+        diff
+    }
+}
+
 /// A LightGBM Booster for making predictions.
 ///
 /// # Thread Safety
@@ -63,7 +175,12 @@ impl FloatInput for f64 {
 /// were fixed in later versions, but the C API does not explicitly document
 /// thread-safety guarantees.
 pub struct Booster {
-    handle: sys::BoosterHandle,
+    pub(crate) handle: sys::BoosterHandle,
+    pub(crate) pandas_categorical: Option<PandasCategorical>,
+    /// `pandas_categorical` trailer of the loaded model text, written back on save
+    pandas_categorical_json: Option<String>,
+    /// Number of leaves of each tree, read from the model text at load
+    pub(crate) leaf_counts: Vec<i32>,
 }
 
 // NOTE: We do NOT implement Send or Sync for Booster because:
@@ -75,25 +192,14 @@ pub struct Booster {
 
 impl Booster {
     /// Load a model from a file
+    ///
+    /// The file is read once and loaded with [`load_from_string`](Self::load_from_string),
+    /// which also reads the Python package's `pandas_categorical` trailer.
     pub fn load<P: AsRef<Path>>(path: P) -> LightGBMResult<Self> {
-        let path_str = path.as_ref().to_str().ok_or_else(|| LightGBMError {
-            description: "Path contains invalid UTF-8 characters".to_string(),
-        })?;
-        let path_c_str = CString::new(path_str).map_err(|e| LightGBMError {
-            description: format!("Path contains NUL byte: {}", e),
-        })?;
-        let mut handle: sys::BoosterHandle = ptr::null_mut();
-        let mut num_iterations = 0i32;
-
-        LightGBMError::check_return_value(unsafe {
-            sys::LGBM_BoosterCreateFromModelfile(
-                path_c_str.as_ptr(),
-                &mut num_iterations,
-                &mut handle,
-            )
+        let model_str = fs::read_to_string(path.as_ref()).map_err(|e| LightGBMError {
+            description: format!("Failed to read model file: {}", e),
         })?;
-
-        Ok(Booster { handle })
+        Self::load_from_string(&model_str)
     }
 
     /// Load a model from a string buffer
@@ -124,7 +230,13 @@ impl Booster {
             )
         })?;
 
-        Ok(Booster { handle })
+        // LightGBM ignores the Python package's metadata trailer, so read it ourselves
+        Ok(Booster {
+            handle,
+            pandas_categorical: PandasCategorical::from_model_str(model_str),
+            pandas_categorical_json: pandas_categorical_json(model_str).map(str::to_string),
+            leaf_counts: leaf_counts(model_str),
+        })
     }
 
     /// Load a model from a byte buffer
@@ -148,6 +260,119 @@ impl Booster {
         Self::load_from_string(model_str)
     }
 
+    /// Save the model to a file
+    ///
+    /// The `pandas_categorical` metadata of the loaded model, if any, is written back too.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::{Booster, SaveOptions};
+    ///
+    /// let booster = Booster::load("model.txt").unwrap();
+    /// booster.save_model("copy.txt", &SaveOptions::default()).unwrap();
+    /// ```
+    pub fn save_model<P: AsRef<Path>>(&self, path: P, options: &SaveOptions) -> LightGBMResult<()> {
+        let path_str = path.as_ref().to_str().ok_or_else(|| LightGBMError {
+            description: "Path contains invalid UTF-8 characters".to_string(),
+        })?;
+        let path_c_str = CString::new(path_str).map_err(|e| LightGBMError {
+            description: format!("Path contains NUL byte: {}", e),
+        })?;
+
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterSaveModel(
+                self.handle,
+                options.start_iteration,
+                options.num_iteration,
+                options.importance_type.to_c_api(),
+                path_c_str.as_ptr(),
+            )
+        })?;
+
+        if let Some(pandas_categorical) = &self.pandas_categorical_json {
+            let mut file = fs::OpenOptions::new()
+                .append(true)
+                .open(path.as_ref())
+                .map_err(|e| LightGBMError {
+                    description: format!("Failed to open saved model file: {}", e),
+                })?;
+            write!(file, "\npandas_categorical:{}\n", pandas_categorical).map_err(|e| {
+                LightGBMError {
+                    description: format!("Failed to write pandas_categorical metadata: {}", e),
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Save the model to a string in LightGBM text format
+    ///
+    /// The `pandas_categorical` metadata of the loaded model, if any, is appended too.
+    pub fn save_model_to_string(&self, options: &SaveOptions) -> LightGBMResult<String> {
+        let mut model_str = self.read_string(|buffer_len, out_len, out_str| unsafe {
+            sys::LGBM_BoosterSaveModelToString(
+                self.handle,
+                options.start_iteration,
+                options.num_iteration,
+                options.importance_type.to_c_api(),
+                buffer_len,
+                out_len,
+                out_str,
+            )
+        })?;
+
+        if let Some(pandas_categorical) = &self.pandas_categorical_json {
+            model_str.push_str("\npandas_categorical:");
+            model_str.push_str(pandas_categorical);
+            model_str.push('\n');
+        }
+
+        Ok(model_str)
+    }
+
+    /// Read a string from a LightGBM function that reports the required buffer size
+    ///
+    /// The first call only queries the length (including the NUL terminator), the second
+    /// call fills a buffer of exactly that size.
+    pub(crate) fn read_string<F>(&self, mut call: F) -> LightGBMResult<String>
+    where
+        F: FnMut(i64, *mut i64, *mut std::os::raw::c_char) -> i32,
+    {
+        let mut out_len = 0i64;
+        let mut buffer: Vec<u8> = vec![0u8; 1];
+        LightGBMError::check_return_value(call(
+            0,
+            &mut out_len,
+            buffer.as_mut_ptr() as *mut std::os::raw::c_char,
+        ))?;
+
+        let buffer_len = out_len.max(1);
+        buffer.resize(buffer_len as usize, 0);
+        LightGBMError::check_return_value(call(
+            buffer_len,
+            &mut out_len,
+            buffer.as_mut_ptr() as *mut std::os::raw::c_char,
+        ))?;
+
+        // The content may have grown between the calls, never read past our buffer
+        if out_len > buffer_len {
+            return Err(LightGBMError {
+                description: format!(
+                    "LightGBM output grew from {} to {} bytes between calls",
+                    buffer_len, out_len
+                ),
+            });
+        }
+
+        let text = CStr::from_bytes_until_nul(&buffer).map_err(|_| LightGBMError {
+            description: "LightGBM output is not NUL-terminated".to_string(),
+        })?;
+        text.to_str().map(str::to_owned).map_err(|e| LightGBMError {
+            description: format!("LightGBM output is not valid UTF-8: {}", e),
+        })
+    }
+
     /// Get the number of features
     pub fn num_features(&self) -> LightGBMResult<i32> {
         let mut num_features = 0i32;
@@ -166,6 +391,167 @@ impl Booster {
         Ok(num_classes)
     }
 
+    /// Get the total number of trees in the model
+    ///
+    /// This is the number of iterations times [`num_model_per_iteration`](Self::num_model_per_iteration).
+    pub fn num_trees(&self) -> LightGBMResult<i32> {
+        let mut num_trees = 0i32;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterNumberOfTotalModel(self.handle, &mut num_trees)
+        })?;
+        Ok(num_trees)
+    }
+
+    /// Get the number of trees per iteration (the number of classes for multiclass models, else 1)
+    pub fn num_model_per_iteration(&self) -> LightGBMResult<i32> {
+        let mut num_models = 0i32;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterNumModelPerIteration(self.handle, &mut num_models)
+        })?;
+        Ok(num_models)
+    }
+
+    /// Get the number of boosting iterations in the model
+    pub fn current_iteration(&self) -> LightGBMResult<i32> {
+        let mut iteration = 0i32;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterGetCurrentIteration(self.handle, &mut iteration)
+        })?;
+        Ok(iteration)
+    }
+
+    /// Check whether the model uses linear trees (`linear_tree=true`)
+    pub fn is_linear(&self) -> LightGBMResult<bool> {
+        let mut is_linear = 0i32;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterGetLinear(self.handle, &mut is_linear)
+        })?;
+        Ok(is_linear != 0)
+    }
+
+    /// Get the largest raw score the model can produce (sum of the largest leaf of every tree)
+    pub fn upper_bound(&self) -> LightGBMResult<f64> {
+        let mut bound = 0.0f64;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterGetUpperBoundValue(self.handle, &mut bound)
+        })?;
+        Ok(bound)
+    }
+
+    /// Get the smallest raw score the model can produce (sum of the smallest leaf of every tree)
+    pub fn lower_bound(&self) -> LightGBMResult<f64> {
+        let mut bound = 0.0f64;
+        LightGBMError::check_return_value(unsafe {
+            sys::LGBM_BoosterGetLowerBoundValue(self.handle, &mut bound)
+        })?;
+        Ok(bound)
+    }
+
+    /// Get the feature names stored in the model, in training column order
+    ///
+    /// Uses `LGBM_BoosterGetFeatureNames`, retrying with a larger buffer for long names.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::Booster;
+    ///
+    /// let booster = Booster::load("model.txt").unwrap();
+    /// for (i, name) in booster.feature_names().unwrap().iter().enumerate() {
+    ///     println!("{}: {}", i, name);
+    /// }
+    /// ```
+    pub fn feature_names(&self) -> LightGBMResult<Vec<String>> {
+        let num_features = self.num_features()?;
+        let mut buffer_len = 256usize;
+
+        loop {
+            let mut buffers: Vec<Vec<u8>> =
+                (0..num_features).map(|_| vec![0u8; buffer_len]).collect();
+            let mut ptrs: Vec<*mut std::os::raw::c_char> = buffers
+                .iter_mut()
+                .map(|b| b.as_mut_ptr() as *mut std::os::raw::c_char)
+                .collect();
+            let mut out_len = 0i32;
+            let mut out_buffer_len = 0usize;
+
+            LightGBMError::check_return_value(unsafe {
+                sys::LGBM_BoosterGetFeatureNames(
+                    self.handle,
+                    num_features,
+                    &mut out_len,
+                    buffer_len,
+                    &mut out_buffer_len,
+                    ptrs.as_mut_ptr(),
+                )
+            })?;
+
+            // Names longer than the buffer were truncated, retry with the required size
+            if out_buffer_len > buffer_len {
+                buffer_len = out_buffer_len;
+                continue;
+            }
+
+            return buffers
+                .iter()
+                .take(out_len as usize)
+                .map(|b| {
+                    CStr::from_bytes_until_nul(b)
+                        .ok()
+                        .and_then(|c| c.to_str().ok())
+                        .map(str::to_owned)
+                        .ok_or_else(|| LightGBMError {
+                            description: "Feature name is not valid UTF-8".to_string(),
+                        })
+                })
+                .collect();
+        }
+    }
+
+    /// Check input column names against the model's feature names
+    ///
+    /// Uses `LGBM_BoosterValidateFeatureNames`, and on mismatch reports which names are
+    /// missing, extra or in a different position.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::Booster;
+    ///
+    /// let booster = Booster::load("model.txt").unwrap();
+    /// let diff = booster.validate_feature_names(&["age", "income", "country"]).unwrap();
+    /// if !diff.is_match() {
+    ///     println!("missing: {:?}, extra: {:?}", diff.missing, diff.extra);
+    /// }
+    /// ```
+    pub fn validate_feature_names(&self, names: &[&str]) -> LightGBMResult<FeatureNameDiff> {
+        let c_names = names
+            .iter()
+            .map(|n| CString::new(*n))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| LightGBMError {
+                description: format!("Feature name contains NUL byte: {}", e),
+            })?;
+        let mut ptrs: Vec<*const std::os::raw::c_char> =
+            c_names.iter().map(|n| n.as_ptr()).collect();
+        let num_names = i32::try_from(names.len()).map_err(|_| LightGBMError {
+            description: format!("Too many feature names: {}", names.len()),
+        })?;
+
+        let ret = unsafe {
+            sys::LGBM_BoosterValidateFeatureNames(self.handle, ptrs.as_mut_ptr(), num_names)
+        };
+        let lightgbm_error = match LightGBMError::check_return_value(ret) {
+            Ok(()) => return Ok(FeatureNameDiff::default()),
+            Err(e) => e,
+        };
+
+        let diff = FeatureNameDiff::between(&self.feature_names()?, names);
+        if diff.is_match() {
+            // LightGBM rejected names we consider equal, surface its reason
+            return Err(lightgbm_error);
+        }
+        Ok(diff)
+    }
+
     /// Predict for a dense matrix
     ///
     /// # Arguments
@@ -193,6 +579,31 @@ impl Booster {
         num_rows: i32,
         num_cols: i32,
         predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        self.predict_mat(data, num_rows, num_cols, true, predict_type)
+    }
+
+    /// Predict for a dense matrix stored in either row-major or column-major order
+    pub(crate) fn predict_mat<T: FloatInput>(
+        &self,
+        data: &[T],
+        num_rows: i32,
+        num_cols: i32,
+        is_row_major: bool,
+        predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        self.predict_mat_with_parameter(data, num_rows, num_cols, is_row_major, predict_type, None)
+    }
+
+    /// Predict for a dense matrix, passing extra LightGBM parameters (e.g. `num_threads=1`)
+    pub(crate) fn predict_mat_with_parameter<T: FloatInput>(
+        &self,
+        data: &[T],
+        num_rows: i32,
+        num_cols: i32,
+        is_row_major: bool,
+        predict_type: i32,
+        parameter: Option<&CStr>,
     ) -> LightGBMResult<Vec<f64>> {
         // Validate input size to prevent undefined behavior
         let expected_len = (num_rows as usize).checked_mul(num_cols as usize)
@@ -225,11 +636,11 @@ impl Booster {
                 T::dtype(),
                 num_rows,
                 num_cols,
-                1, // is_row_major
+                is_row_major as i32,
                 predict_type,
                 0,  // start_iteration (0 means from the first)
                 -1, // num_iteration (-1 means use all)
-                ptr::null(),
+                parameter.map_or(ptr::null(), CStr::as_ptr),
                 &mut out_len,
                 ptr::null_mut(),
             )
@@ -246,11 +657,11 @@ impl Booster {
                 T::dtype(),
                 num_rows,
                 num_cols,
-                1, // is_row_major
+                is_row_major as i32,
                 predict_type,
                 0,  // start_iteration
                 -1, // num_iteration
-                ptr::null(),
+                parameter.map_or(ptr::null(), CStr::as_ptr),
                 &mut out_len,
                 out_result.as_mut_ptr(),
             )
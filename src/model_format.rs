@@ -0,0 +1,878 @@
+use crate::error::{LightGBMError, LightGBMResult};
+#[cfg(feature = "ffi")]
+use crate::model::{Booster, SaveOptions};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A LightGBM model in text format, parsed into Rust types
+///
+/// Parsing and writing are pure Rust, no LightGBM library call is involved. Writing a model
+/// that was parsed from LightGBM output reproduces the original text byte for byte; values
+/// changed after parsing are written with 17 significant digits, and `tree_sizes` is always
+/// recomputed so that LightGBM can load the result. A model can also be built from scratch,
+/// starting from [`TextModel::default`] and [`TreeModel::default`]; equality ignores how
+/// values were originally written.
+///
+/// # Example
+/// ```no_run
+/// use lightgbm_rust::model_format::TextModel;
+///
+/// let text = std::fs::read_to_string("model.txt").unwrap();
+/// let mut model = TextModel::parse(&text).unwrap();
+/// println!("{} trees, objective {:?}", model.trees.len(), model.header.objective);
+///
+/// model.trees[0].leaf_value[0] *= 0.5;
+/// std::fs::write("edited.txt", model.to_string()).unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextModel {
+    /// Model-wide settings written before the trees
+    pub header: ModelHeader,
+    /// The trees, in training order
+    pub trees: Vec<TreeModel>,
+    /// `(feature name, importance)` pairs of the `feature_importances:` section
+    ///
+    /// LightGBM writes importances truncated to integers, and so does the writer here.
+    pub feature_importances: Vec<(String, f64)>,
+    /// `(name, value)` pairs of the `parameters:` section, if present
+    pub parameters: Option<Vec<(String, String)>>,
+    /// Raw JSON of the `pandas_categorical:` trailer written by the Python package
+    pub pandas_categorical: Option<String>,
+    /// Lines of sections this parser does not know, kept verbatim
+    pub other_sections: Vec<String>,
+}
+
+/// The header of a text model
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelHeader {
+    /// Model format version, e.g. `v4`
+    pub version: String,
+    /// Number of classes (1 for regression and binary classification)
+    pub num_class: usize,
+    /// Number of trees trained per boosting iteration
+    pub num_tree_per_iteration: usize,
+    /// Index of the label column in the training data
+    pub label_index: i32,
+    /// Largest feature index used by the model
+    pub max_feature_idx: i32,
+    /// Objective with its parameters, e.g. `binary sigmoid:1`
+    pub objective: Option<String>,
+    /// Whether the output is averaged over trees (random forest mode)
+    pub average_output: bool,
+    /// Feature names, in training column order
+    pub feature_names: Vec<String>,
+    /// Monotone constraint per feature, if any were used
+    pub monotone_constraints: Option<Vec<i32>>,
+    /// Value range or categories of each feature seen in training
+    pub feature_infos: Vec<FeatureInfo>,
+    /// Size in bytes of each tree block, as read (recomputed on write)
+    pub tree_sizes: Vec<usize>,
+    /// Unknown `key=value` header lines, kept verbatim
+    pub other: Vec<(String, String)>,
+    raw: RawText,
+}
+
+impl Default for ModelHeader {
+    /// A `v4` header of a single-output model without features or trees
+    fn default() -> Self {
+        ModelHeader {
+            version: "v4".to_string(),
+            num_class: 1,
+            num_tree_per_iteration: 1,
+            label_index: 0,
+            max_feature_idx: -1,
+            objective: None,
+            average_output: false,
+            feature_names: Vec::new(),
+            monotone_constraints: None,
+            feature_infos: Vec::new(),
+            tree_sizes: Vec::new(),
+            other: Vec::new(),
+            raw: RawText::default(),
+        }
+    }
+}
+
+/// Value range or categories of a feature seen in training
+#[derive(Clone, Debug, PartialEq)]
+pub enum FeatureInfo {
+    /// The feature is not used by the model (`none`)
+    Unused,
+    /// Numerical feature with its `[min:max]` range
+    Numerical {
+        /// Smallest value seen
+        min: f64,
+        /// Largest value seen
+        max: f64,
+    },
+    /// Categorical feature with the categories seen
+    Categorical(Vec<i32>),
+}
+
+/// A single tree block (`Tree=N`) of a text model
+///
+/// Arrays follow LightGBM's layout: internal nodes are indexed `0..num_leaves - 1`, and a
+/// negative child `c` refers to leaf `!c` (i.e. `-c - 1`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeModel {
+    /// Number of leaves
+    pub num_leaves: usize,
+    /// Number of categorical splits
+    pub num_cat: usize,
+    /// Feature tested by each internal node
+    pub split_feature: Vec<i32>,
+    /// Gain of each split
+    pub split_gain: Vec<f64>,
+    /// Threshold of each numerical split, or index into `cat_boundaries` for categorical splits
+    pub threshold: Vec<f64>,
+    /// Bit flags of each split: categorical, default left and missing type
+    pub decision_type: Vec<i8>,
+    /// Left child of each internal node
+    pub left_child: Vec<i32>,
+    /// Right child of each internal node
+    pub right_child: Vec<i32>,
+    /// Output of each leaf
+    pub leaf_value: Vec<f64>,
+    /// Sum of hessians of each leaf
+    pub leaf_weight: Vec<f64>,
+    /// Number of training samples of each leaf
+    pub leaf_count: Vec<i64>,
+    /// Output of each internal node if it were a leaf
+    pub internal_value: Vec<f64>,
+    /// Sum of hessians of each internal node
+    pub internal_weight: Vec<f64>,
+    /// Number of training samples of each internal node
+    pub internal_count: Vec<i64>,
+    /// Offsets into `cat_threshold` of each categorical split's bitset (`num_cat + 1` entries)
+    pub cat_boundaries: Vec<i32>,
+    /// Concatenated category bitsets of the categorical splits
+    pub cat_threshold: Vec<u32>,
+    /// Whether leaves are linear models
+    pub is_linear: bool,
+    /// Constant term of each linear leaf
+    pub leaf_const: Vec<f64>,
+    /// Features of each linear leaf
+    pub leaf_features: Vec<Vec<i32>>,
+    /// Coefficients of each linear leaf, one per entry of `leaf_features`
+    pub leaf_coeff: Vec<Vec<f64>>,
+    /// Learning rate applied to the leaf values
+    pub shrinkage: f64,
+    /// Unknown `key=value` lines of the tree block, kept verbatim
+    pub other: Vec<(String, String)>,
+    raw: RawText,
+}
+
+/// Text of parsed values, written back while the values are unchanged
+///
+/// Models compare equal regardless of it, so a parsed model equals the same model built or
+/// edited in code.
+#[derive(Clone, Debug, Default)]
+struct RawText(HashMap<String, String>);
+
+impl PartialEq for RawText {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl std::ops::Deref for RawText {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for RawText {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl TreeModel {
+    /// Number of internal (split) nodes
+    pub fn num_internal(&self) -> usize {
+        self.num_leaves.saturating_sub(1)
+    }
+}
+
+impl TextModel {
+    /// Parse a model from LightGBM text format
+    pub fn parse(text: &str) -> LightGBMResult<Self> {
+        Parser::new(text).parse()
+    }
+
+    /// Parse a model from a file in LightGBM text format
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> LightGBMResult<Self> {
+        let text = std::fs::read_to_string(path.as_ref()).map_err(|e| LightGBMError {
+            description: format!("Failed to read model file: {}", e),
+        })?;
+        Self::parse(&text)
+    }
+}
+
+impl FromStr for TextModel {
+    type Err = LightGBMError;
+
+    fn from_str(text: &str) -> LightGBMResult<Self> {
+        Self::parse(text)
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl Booster {
+    /// Get the model in parsed text format
+    pub fn to_text_model(&self) -> LightGBMResult<TextModel> {
+        TextModel::parse(&self.save_model_to_string(&SaveOptions::default())?)
+    }
+}
+
+struct Parser<'a> {
+    lines: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        let mut lines: Vec<&str> = text.split('\n').collect();
+        // A trailing newline yields one empty last element
+        if lines.last() == Some(&"") {
+            lines.pop();
+        }
+        Parser { lines, pos: 0 }
+    }
+
+    fn error(&self, message: impl fmt::Display) -> LightGBMError {
+        LightGBMError {
+            description: format!("Invalid model text at line {}: {}", self.pos + 1, message),
+        }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.lines.get(self.pos).copied()
+    }
+
+    fn skip_blank(&mut self) {
+        while self.peek() == Some("") {
+            self.pos += 1;
+        }
+    }
+
+    /// Read `key=value` lines up to the next blank line
+    fn read_block(&mut self) -> Vec<(&'a str, &'a str)> {
+        let mut entries = Vec::new();
+        while let Some(line) = self.peek() {
+            if line.is_empty() {
+                break;
+            }
+            entries.push(line.split_once('=').unwrap_or((line, "")));
+            self.pos += 1;
+        }
+        entries
+    }
+
+    fn parse(mut self) -> LightGBMResult<TextModel> {
+        if self.peek() != Some("tree") {
+            return Err(self.error("expected 'tree' as the first line"));
+        }
+        self.pos += 1;
+        let header = self.parse_header()?;
+
+        let mut trees = Vec::new();
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                Some(line) if line.starts_with("Tree=") => {
+                    self.pos += 1;
+                    let start = self.pos;
+                    let entries = self.read_block();
+                    trees.push(parse_tree(&entries).map_err(|e| LightGBMError {
+                        description: format!(
+                            "Invalid model text in tree starting at line {}: {}",
+                            start + 1,
+                            e
+                        ),
+                    })?);
+                }
+                Some("end of trees") => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(line) => return Err(self.error(format!("unexpected line '{}'", line))),
+                None => return Err(self.error("missing 'end of trees'")),
+            }
+        }
+
+        let mut model = TextModel {
+            header,
+            trees,
+            feature_importances: Vec::new(),
+            parameters: None,
+            pandas_categorical: None,
+            other_sections: Vec::new(),
+        };
+        self.parse_trailer(&mut model)?;
+        Ok(model)
+    }
+
+    fn parse_header(&mut self) -> LightGBMResult<ModelHeader> {
+        let mut header = ModelHeader {
+            version: String::new(),
+            ..ModelHeader::default()
+        };
+
+        for (key, value) in self.read_block() {
+            match key {
+                "version" => header.version = value.to_string(),
+                "num_class" => header.num_class = parse_value(key, value)?,
+                "num_tree_per_iteration" => {
+                    header.num_tree_per_iteration = parse_value(key, value)?
+                }
+                "label_index" => header.label_index = parse_value(key, value)?,
+                "max_feature_idx" => header.max_feature_idx = parse_value(key, value)?,
+                "objective" => header.objective = Some(value.to_string()),
+                "average_output" => header.average_output = true,
+                "feature_names" => header.feature_names = split_tokens(value),
+                "monotone_constraints" => {
+                    header.monotone_constraints = Some(parse_array(key, value)?)
+                }
+                "feature_infos" => {
+                    header.feature_infos = value
+                        .split(' ')
+                        .filter(|t| !t.is_empty())
+                        .map(parse_feature_info)
+                        .collect::<LightGBMResult<_>>()?;
+                    header.raw.insert(key.to_string(), value.to_string());
+                }
+                "tree_sizes" => header.tree_sizes = parse_array(key, value)?,
+                _ => header.other.push((key.to_string(), value.to_string())),
+            }
+        }
+
+        Ok(header)
+    }
+
+    fn parse_trailer(&mut self, model: &mut TextModel) -> LightGBMResult<()> {
+        while let Some(line) = self.peek() {
+            self.pos += 1;
+            match line {
+                "" => {}
+                "feature_importances:" => {
+                    for (name, value) in self.read_block() {
+                        let value = value
+                            .parse::<f64>()
+                            .map_err(|e| self.error(format!("invalid importance: {}", e)))?;
+                        model.feature_importances.push((name.to_string(), value));
+                    }
+                }
+                "parameters:" => {
+                    let mut parameters = Vec::new();
+                    loop {
+                        match self.peek() {
+                            Some("end of parameters") => {
+                                self.pos += 1;
+                                break;
+                            }
+                            Some("") => self.pos += 1,
+                            Some(line) => {
+                                let (name, value) = line
+                                    .strip_prefix('[')
+                                    .and_then(|l| l.strip_suffix(']'))
+                                    .and_then(|l| l.split_once(": "))
+                                    .ok_or_else(|| {
+                                        self.error(format!("invalid parameter line '{}'", line))
+                                    })?;
+                                parameters.push((name.to_string(), value.to_string()));
+                                self.pos += 1;
+                            }
+                            None => return Err(self.error("missing 'end of parameters'")),
+                        }
+                    }
+                    model.parameters = Some(parameters);
+                }
+                _ => match line.strip_prefix("pandas_categorical:") {
+                    Some(json) => model.pandas_categorical = Some(json.to_string()),
+                    None => model.other_sections.push(line.to_string()),
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_tree(entries: &[(&str, &str)]) -> LightGBMResult<TreeModel> {
+    let mut tree = TreeModel {
+        shrinkage: 1.0,
+        ..TreeModel::default()
+    };
+    let mut num_features: Vec<usize> = Vec::new();
+    let mut leaf_features_raw = "";
+    let mut leaf_coeff_raw = "";
+
+    for &(key, value) in entries {
+        match key {
+            "num_leaves" => tree.num_leaves = parse_value(key, value)?,
+            "num_cat" => tree.num_cat = parse_value(key, value)?,
+            "split_feature" => tree.split_feature = parse_array(key, value)?,
+            "split_gain" => tree.split_gain = parse_array(key, value)?,
+            "threshold" => tree.threshold = parse_array(key, value)?,
+            "decision_type" => tree.decision_type = parse_array(key, value)?,
+            "left_child" => tree.left_child = parse_array(key, value)?,
+            "right_child" => tree.right_child = parse_array(key, value)?,
+            "leaf_value" => tree.leaf_value = parse_array(key, value)?,
+            "leaf_weight" => tree.leaf_weight = parse_array(key, value)?,
+            "leaf_count" => tree.leaf_count = parse_array(key, value)?,
+            "internal_value" => tree.internal_value = parse_array(key, value)?,
+            "internal_weight" => tree.internal_weight = parse_array(key, value)?,
+            "internal_count" => tree.internal_count = parse_array(key, value)?,
+            "cat_boundaries" => tree.cat_boundaries = parse_array(key, value)?,
+            "cat_threshold" => tree.cat_threshold = parse_array(key, value)?,
+            "is_linear" => tree.is_linear = parse_value::<i32>(key, value)? != 0,
+            "leaf_const" => tree.leaf_const = parse_array(key, value)?,
+            "num_features" => num_features = parse_array(key, value)?,
+            "leaf_features" => leaf_features_raw = value,
+            "leaf_coeff" => leaf_coeff_raw = value,
+            "shrinkage" => tree.shrinkage = parse_value(key, value)?,
+            _ => {
+                tree.other.push((key.to_string(), value.to_string()));
+                continue;
+            }
+        }
+        tree.raw.insert(key.to_string(), value.to_string());
+    }
+
+    if tree.is_linear {
+        tree.leaf_features = split_by_counts(
+            parse_array("leaf_features", leaf_features_raw)?,
+            &num_features,
+        )?;
+        tree.leaf_coeff =
+            split_by_counts(parse_array("leaf_coeff", leaf_coeff_raw)?, &num_features)?;
+    }
+
+    let internal = tree.num_internal();
+    let arrays = [
+        ("split_feature", tree.split_feature.len(), internal),
+        ("threshold", tree.threshold.len(), internal),
+        ("decision_type", tree.decision_type.len(), internal),
+        ("left_child", tree.left_child.len(), internal),
+        ("right_child", tree.right_child.len(), internal),
+        ("leaf_value", tree.leaf_value.len(), tree.num_leaves),
+    ];
+    for (name, len, expected) in arrays {
+        if len != expected {
+            return Err(LightGBMError {
+                description: format!(
+                    "{} has {} entries, expected {} for {} leaves",
+                    name, len, expected, tree.num_leaves
+                ),
+            });
+        }
+    }
+
+    Ok(tree)
+}
+
+fn parse_feature_info(token: &str) -> LightGBMResult<FeatureInfo> {
+    if token == "none" {
+        return Ok(FeatureInfo::Unused);
+    }
+    if let Some(range) = token.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        let (min, max) = range.split_once(':').ok_or_else(|| LightGBMError {
+            description: format!("Invalid feature info '{}'", token),
+        })?;
+        return Ok(FeatureInfo::Numerical {
+            min: parse_value("feature_infos", min)?,
+            max: parse_value("feature_infos", max)?,
+        });
+    }
+    token
+        .split(':')
+        .map(|c| parse_value("feature_infos", c))
+        .collect::<LightGBMResult<_>>()
+        .map(FeatureInfo::Categorical)
+}
+
+fn parse_value<T: FromStr>(key: &str, value: &str) -> LightGBMResult<T>
+where
+    T::Err: fmt::Display,
+{
+    value.trim().parse::<T>().map_err(|e| LightGBMError {
+        description: format!("Invalid {} value '{}': {}", key, value, e),
+    })
+}
+
+fn parse_array<T: FromStr>(key: &str, value: &str) -> LightGBMResult<Vec<T>>
+where
+    T::Err: fmt::Display,
+{
+    value
+        .split(' ')
+        .filter(|t| !t.is_empty())
+        .map(|t| parse_value(key, t))
+        .collect()
+}
+
+fn split_tokens(value: &str) -> Vec<String> {
+    value
+        .split(' ')
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn split_by_counts<T>(values: Vec<T>, counts: &[usize]) -> LightGBMResult<Vec<Vec<T>>> {
+    if counts.iter().sum::<usize>() != values.len() {
+        return Err(LightGBMError {
+            description: format!(
+                "Linear leaves list {} values but num_features adds up to {}",
+                values.len(),
+                counts.iter().sum::<usize>()
+            ),
+        });
+    }
+    let mut values = values.into_iter();
+    Ok(counts
+        .iter()
+        .map(|&n| values.by_ref().take(n).collect())
+        .collect())
+}
+
+impl fmt::Display for TextModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree_blocks: Vec<String> = self
+            .trees
+            .iter()
+            .enumerate()
+            .map(|(i, tree)| format!("Tree={}\n{}\n", i, tree))
+            .collect();
+
+        let header = &self.header;
+        writeln!(f, "tree")?;
+        writeln!(f, "version={}", header.version)?;
+        writeln!(f, "num_class={}", header.num_class)?;
+        writeln!(
+            f,
+            "num_tree_per_iteration={}",
+            header.num_tree_per_iteration
+        )?;
+        writeln!(f, "label_index={}", header.label_index)?;
+        writeln!(f, "max_feature_idx={}", header.max_feature_idx)?;
+        if let Some(objective) = &header.objective {
+            writeln!(f, "objective={}", objective)?;
+        }
+        if header.average_output {
+            writeln!(f, "average_output")?;
+        }
+        writeln!(f, "feature_names={}", header.feature_names.join(" "))?;
+        if let Some(constraints) = &header.monotone_constraints {
+            writeln!(
+                f,
+                "monotone_constraints={}",
+                join(constraints, i32::to_string)
+            )?;
+        }
+        writeln!(
+            f,
+            "feature_infos={}",
+            keep_or_format(
+                header.raw.get("feature_infos"),
+                &header.feature_infos,
+                |raw| raw
+                    .split(' ')
+                    .filter(|t| !t.is_empty())
+                    .map(parse_feature_info)
+                    .collect::<LightGBMResult<Vec<_>>>()
+                    .ok(),
+                |infos| join(infos, format_feature_info),
+            )
+        )?;
+        for (key, value) in &header.other {
+            writeln!(f, "{}={}", key, value)?;
+        }
+        writeln!(
+            f,
+            "tree_sizes={}",
+            join(&tree_blocks, |b| b.len().to_string())
+        )?;
+        writeln!(f)?;
+
+        for block in &tree_blocks {
+            f.write_str(block)?;
+        }
+        writeln!(f, "end of trees")?;
+
+        writeln!(f)?;
+        writeln!(f, "feature_importances:")?;
+        for (name, value) in &self.feature_importances {
+            // LightGBM writes `static_cast<size_t>(importance)`
+            writeln!(f, "{}={}", name, *value as u64)?;
+        }
+
+        if let Some(parameters) = &self.parameters {
+            writeln!(f)?;
+            writeln!(f, "parameters:")?;
+            for (name, value) in parameters {
+                writeln!(f, "[{}: {}]", name, value)?;
+            }
+            writeln!(f)?;
+            writeln!(f, "end of parameters")?;
+        }
+
+        for line in &self.other_sections {
+            writeln!(f, "{}", line)?;
+        }
+
+        if let Some(json) = &self.pandas_categorical {
+            writeln!(f)?;
+            writeln!(f, "pandas_categorical:{}", json)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for TreeModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let floats = |key: &str, values: &[f64]| {
+            keep_or_format(
+                self.raw.get(key),
+                &values,
+                |raw| parse_array::<f64>(key, raw).ok(),
+                |v| join(v, |x| format_g(*x, 17)),
+            )
+        };
+
+        writeln!(f, "num_leaves={}", self.num_leaves)?;
+        writeln!(f, "num_cat={}", self.num_cat)?;
+        writeln!(
+            f,
+            "split_feature={}",
+            join(&self.split_feature, i32::to_string)
+        )?;
+        writeln!(f, "split_gain={}", floats("split_gain", &self.split_gain))?;
+        writeln!(f, "threshold={}", floats("threshold", &self.threshold))?;
+        writeln!(
+            f,
+            "decision_type={}",
+            join(&self.decision_type, i8::to_string)
+        )?;
+        writeln!(f, "left_child={}", join(&self.left_child, i32::to_string))?;
+        writeln!(f, "right_child={}", join(&self.right_child, i32::to_string))?;
+        writeln!(f, "leaf_value={}", floats("leaf_value", &self.leaf_value))?;
+        writeln!(
+            f,
+            "leaf_weight={}",
+            floats("leaf_weight", &self.leaf_weight)
+        )?;
+        writeln!(f, "leaf_count={}", join(&self.leaf_count, i64::to_string))?;
+        writeln!(
+            f,
+            "internal_value={}",
+            floats("internal_value", &self.internal_value)
+        )?;
+        writeln!(
+            f,
+            "internal_weight={}",
+            floats("internal_weight", &self.internal_weight)
+        )?;
+        writeln!(
+            f,
+            "internal_count={}",
+            join(&self.internal_count, i64::to_string)
+        )?;
+        if self.num_cat > 0 {
+            writeln!(
+                f,
+                "cat_boundaries={}",
+                join(&self.cat_boundaries, i32::to_string)
+            )?;
+            writeln!(
+                f,
+                "cat_threshold={}",
+                join(&self.cat_threshold, u32::to_string)
+            )?;
+        }
+        writeln!(f, "is_linear={}", self.is_linear as i32)?;
+        if self.is_linear {
+            writeln!(f, "leaf_const={}", floats("leaf_const", &self.leaf_const))?;
+            writeln!(
+                f,
+                "num_features={}",
+                join(&self.leaf_features, |features| features.len().to_string())
+            )?;
+            // Each leaf's list is followed by an extra space, as LightGBM writes it
+            write!(f, "leaf_features=")?;
+            for features in &self.leaf_features {
+                if !features.is_empty() {
+                    write!(f, "{} ", join(features, i32::to_string))?;
+                }
+                write!(f, " ")?;
+            }
+            writeln!(f)?;
+            write!(f, "leaf_coeff=")?;
+            let flat: Vec<f64> = self.leaf_coeff.iter().flatten().copied().collect();
+            let unchanged = self
+                .raw
+                .get("leaf_coeff")
+                .and_then(|raw| parse_array::<f64>("leaf_coeff", raw).ok())
+                .is_some_and(|parsed| same_floats(&parsed, &flat));
+            match self.raw.get("leaf_coeff") {
+                Some(raw) if unchanged => write!(f, "{}", raw)?,
+                _ => {
+                    for coeff in &self.leaf_coeff {
+                        if !coeff.is_empty() {
+                            write!(f, "{} ", join(coeff, |x| format_g(*x, 17)))?;
+                        }
+                        write!(f, " ")?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        for (key, value) in &self.other {
+            writeln!(f, "{}={}", key, value)?;
+        }
+        writeln!(
+            f,
+            "shrinkage={}",
+            keep_or_format(
+                self.raw.get("shrinkage"),
+                &self.shrinkage,
+                |raw| raw.trim().parse::<f64>().ok(),
+                |x| format_g(*x, 6),
+            )
+        )?;
+        writeln!(f)
+    }
+}
+
+/// Write the original text of a value if it still parses to the current value
+fn keep_or_format<T: SameValue + ?Sized>(
+    raw: Option<&String>,
+    current: &T,
+    parse: impl Fn(&str) -> Option<T::Owned>,
+    format: impl Fn(&T) -> String,
+) -> String {
+    match raw {
+        Some(raw) if parse(raw).is_some_and(|parsed| current.same_value(&parsed)) => raw.clone(),
+        _ => format(current),
+    }
+}
+
+/// Equality that treats NaN as equal to itself, so unchanged NaNs keep their text
+trait SameValue {
+    type Owned;
+
+    fn same_value(&self, other: &Self::Owned) -> bool;
+}
+
+impl SameValue for f64 {
+    type Owned = f64;
+
+    fn same_value(&self, other: &f64) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl SameValue for &[f64] {
+    type Owned = Vec<f64>;
+
+    fn same_value(&self, other: &Vec<f64>) -> bool {
+        same_floats(self, other)
+    }
+}
+
+impl SameValue for Vec<FeatureInfo> {
+    type Owned = Vec<FeatureInfo>;
+
+    fn same_value(&self, other: &Vec<FeatureInfo>) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other).all(|(a, b)| match (a, b) {
+                (
+                    FeatureInfo::Numerical { min, max },
+                    FeatureInfo::Numerical {
+                        min: other_min,
+                        max: other_max,
+                    },
+                ) => min.to_bits() == other_min.to_bits() && max.to_bits() == other_max.to_bits(),
+                _ => a == b,
+            })
+    }
+}
+
+fn same_floats(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.to_bits() == y.to_bits())
+}
+
+fn join<T>(values: &[T], format: impl Fn(&T) -> String) -> String {
+    values.iter().map(format).collect::<Vec<_>>().join(" ")
+}
+
+fn format_feature_info(info: &FeatureInfo) -> String {
+    match info {
+        FeatureInfo::Unused => "none".to_string(),
+        FeatureInfo::Numerical { min, max } => {
+            format!("[{}:{}]", format_g(*min, 17), format_g(*max, 17))
+        }
+        FeatureInfo::Categorical(categories) => categories
+            .iter()
+            .map(i32::to_string)
+            .collect::<Vec<_>>()
+            .join(":"),
+    }
+}
+
+/// Largest `f32` not above `value`, so that `x <= threshold` holds for the same `f32` values
+#[cfg(any(all(feature = "native", feature = "embedded"), feature = "onnx"))]
+pub(crate) fn threshold_to_f32(value: f64) -> f32 {
+    let rounded = value as f32;
+    if (rounded as f64) <= value || rounded.is_nan() {
+        return rounded;
+    }
+    // Step one `f32` towards negative infinity
+    if rounded == 0.0 {
+        -f32::from_bits(1)
+    } else if rounded > 0.0 {
+        f32::from_bits(rounded.to_bits() - 1)
+    } else {
+        f32::from_bits(rounded.to_bits() + 1)
+    }
+}
+
+/// Format a float like C's `%.{precision}g`, which is how LightGBM writes doubles
+pub(crate) fn format_g(value: f64, precision: usize) -> String {
+    if value.is_nan() {
+        return "nan".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    let precision = precision.max(1);
+    // Rounding to `precision` significant digits decides the exponent
+    let scientific = format!("{:.*e}", precision - 1, value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+
+    if exponent < -4 || exponent >= precision as i32 {
+        let mantissa = trim_fraction(mantissa);
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+    } else {
+        let decimals = (precision as i32 - 1 - exponent).max(0) as usize;
+        trim_fraction(&format!("{:.*}", decimals, value)).to_string()
+    }
+}
+
+fn trim_fraction(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
@@ -0,0 +1,103 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::Booster;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+/// How [`Booster::predict_named`] handles keys that are not features of the model
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnknownFeatures {
+    /// Fail the whole prediction, listing the unknown keys
+    Error,
+    /// Skip unknown keys, logging them with `log::warn!`
+    Warn,
+    /// Skip unknown keys silently
+    Ignore,
+}
+
+impl Booster {
+    /// Predict for rows given as feature name to value pairs
+    ///
+    /// Each row is any collection of `(name, value)` pairs, such as a `HashMap<&str, f64>`
+    /// or `BTreeMap<String, f64>` (owned or borrowed). Values are placed in the column of
+    /// the matching model feature; features absent from a row are set to NaN and treated
+    /// as missing by LightGBM.
+    ///
+    /// # Arguments
+    /// * `rows` - Rows to predict, one map-like collection per sample
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    /// * `unknown` - Whether keys that are not model features are an error, a warning or
+    ///   ignored
+    ///
+    /// # Returns
+    /// Vector of predictions
+    ///
+    /// # Example
+    /// ```ignore
+    /// use lightgbm_rust::UnknownFeatures;
+    /// use std::collections::HashMap;
+    ///
+    /// let row = HashMap::from([("age", 31.0), ("country_code", 4.0)]);
+    /// let predictions = booster.predict_named([row], predict_type::NORMAL, UnknownFeatures::Error)?;
+    /// ```
+    pub fn predict_named<I, R, K, V>(
+        &self,
+        rows: I,
+        predict_type: i32,
+        unknown: UnknownFeatures,
+    ) -> LightGBMResult<Vec<f64>>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Borrow<f64>,
+    {
+        let feature_names = self.feature_names()?;
+        let num_cols = feature_names.len();
+        let columns: HashMap<&str, usize> = feature_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+
+        let mut data = Vec::new();
+        let mut num_rows = 0usize;
+        let mut unknown_keys = Vec::new();
+
+        for row in rows {
+            let offset = data.len();
+            data.resize(offset + num_cols, f64::NAN);
+
+            for (key, value) in row {
+                let key = key.as_ref();
+                match columns.get(key) {
+                    Some(&col) => data[offset + col] = *value.borrow(),
+                    None => {
+                        if unknown != UnknownFeatures::Ignore
+                            && !unknown_keys.iter().any(|k| k == key)
+                        {
+                            unknown_keys.push(key.to_string());
+                        }
+                    }
+                }
+            }
+            num_rows += 1;
+        }
+
+        if !unknown_keys.is_empty() {
+            if unknown == UnknownFeatures::Error {
+                return Err(LightGBMError {
+                    description: format!("Unknown features: {}", unknown_keys.join(", ")),
+                });
+            }
+            log::warn!(
+                "Ignoring input keys that are not model features: {}",
+                unknown_keys.join(", ")
+            );
+        }
+
+        let num_rows = i32::try_from(num_rows).map_err(|_| LightGBMError {
+            description: format!("Too many rows for a single prediction: {}", num_rows),
+        })?;
+        self.predict(&data, num_rows, num_cols as i32, predict_type)
+    }
+}
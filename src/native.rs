@@ -0,0 +1,491 @@
+use crate::decision_type::{
+    categorical_goes_left, is_zero, missing_type, CATEGORICAL_MASK, DEFAULT_LEFT_MASK,
+    MISSING_TYPE_NAN, MISSING_TYPE_ZERO,
+};
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model_format::{TextModel, TreeModel};
+use crate::objective::Objective;
+use crate::predict_type;
+use std::path::Path;
+
+mod batch;
+#[cfg(feature = "embedded")]
+mod compact;
+mod shap;
+
+pub use self::batch::BatchPredictor;
+
+/// A model evaluated in pure Rust, without the LightGBM library
+///
+/// Built from a parsed text model, it reproduces [`Booster::predict`](crate::Booster::predict)
+/// for row-major input, including missing value handling and the objective's output
+/// transform. Leaf values are summed in the same order as LightGBM, so results agree with
+/// the C API up to floating-point rounding of the transform.
+///
+/// # Example
+/// ```no_run
+/// use lightgbm_rust::{predict_type, NativeBooster};
+///
+/// let booster = NativeBooster::load("model.txt").unwrap();
+/// let data = vec![1.0f64, 2.0, 3.0, 4.0];
+/// let predictions = booster.predict(&data, 1, 4, predict_type::NORMAL).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct NativeBooster {
+    trees: Vec<NativeTree>,
+    objective: Objective,
+    num_features: usize,
+    num_class: usize,
+    num_tree_per_iteration: usize,
+    average_output: bool,
+}
+
+/// A tree in LightGBM's array layout, checked so that traversal always terminates
+#[derive(Clone, Debug)]
+struct NativeTree {
+    split_feature: Vec<usize>,
+    threshold: Vec<f64>,
+    decision_type: Vec<u8>,
+    left_child: Vec<i32>,
+    right_child: Vec<i32>,
+    leaf_value: Vec<f64>,
+    cat_boundaries: Vec<usize>,
+    cat_threshold: Vec<u32>,
+    linear: Option<LinearLeaves>,
+    /// Training samples per leaf and per internal node, for SHAP values
+    leaf_count: Vec<f64>,
+    internal_count: Vec<f64>,
+    max_depth: usize,
+}
+
+/// Linear models of the leaves of a tree trained with `linear_tree=true`
+#[derive(Clone, Debug)]
+struct LinearLeaves {
+    leaf_const: Vec<f64>,
+    leaf_features: Vec<Vec<usize>>,
+    leaf_coeff: Vec<Vec<f64>>,
+}
+
+impl NativeBooster {
+    /// Build the evaluator from a parsed text model
+    pub fn from_text_model(model: &TextModel) -> LightGBMResult<Self> {
+        let header = &model.header;
+        let num_features = usize::try_from(header.max_feature_idx + 1).unwrap_or(0);
+        let num_tree_per_iteration = header.num_tree_per_iteration.max(1);
+        if model.trees.len() % num_tree_per_iteration != 0 {
+            return Err(LightGBMError {
+                description: format!(
+                    "Model has {} trees, not a multiple of {} trees per iteration",
+                    model.trees.len(),
+                    num_tree_per_iteration
+                ),
+            });
+        }
+
+        let trees = model
+            .trees
+            .iter()
+            .enumerate()
+            .map(|(i, tree)| {
+                NativeTree::new(tree, num_features).map_err(|e| LightGBMError {
+                    description: format!("Tree {}: {}", i, e),
+                })
+            })
+            .collect::<LightGBMResult<_>>()?;
+
+        Ok(NativeBooster {
+            trees,
+            objective: Objective::parse(header.objective.as_deref())?,
+            num_features,
+            num_class: header.num_class,
+            num_tree_per_iteration,
+            average_output: header.average_output,
+        })
+    }
+
+    /// Load a model from a file in LightGBM text format
+    pub fn load<P: AsRef<Path>>(path: P) -> LightGBMResult<Self> {
+        Self::from_text_model(&TextModel::load(path)?)
+    }
+
+    /// Load a model from a string in LightGBM text format
+    pub fn load_from_string(model_str: &str) -> LightGBMResult<Self> {
+        Self::from_text_model(&TextModel::parse(model_str)?)
+    }
+
+    /// Get the number of features the model expects
+    pub fn num_features(&self) -> i32 {
+        self.num_features as i32
+    }
+
+    /// Get the number of classes
+    pub fn num_classes(&self) -> i32 {
+        self.num_class as i32
+    }
+
+    /// Get the total number of trees (iterations × trees per iteration)
+    pub fn num_trees(&self) -> i32 {
+        self.trees.len() as i32
+    }
+
+    /// Predict for a dense row-major matrix
+    ///
+    /// Takes the same arguments and returns the same layout as
+    /// [`Booster::predict`](crate::Booster::predict). All prediction types are supported;
+    /// [`CONTRIB`](predict_type::CONTRIB) computes SHAP values with TreeSHAP as LightGBM
+    /// does, which is not available for linear trees.
+    pub fn predict<T: Copy + Into<f64>>(
+        &self,
+        data: &[T],
+        num_rows: i32,
+        num_cols: i32,
+        predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        let num_rows = self.check_shape(data.len(), num_rows, num_cols)?;
+        let per_row = match predict_type {
+            predict_type::NORMAL | predict_type::RAW_SCORE => self.num_tree_per_iteration,
+            predict_type::LEAF_INDEX => self.trees.len(),
+            predict_type::CONTRIB => {
+                self.check_shap_support()?;
+                self.num_tree_per_iteration * (self.num_features + 1)
+            }
+            _ => {
+                return Err(LightGBMError {
+                    description: format!("Unknown prediction type {}", predict_type),
+                })
+            }
+        };
+
+        let mut output = vec![0.0f64; num_rows * per_row];
+        let mut row = vec![0.0f64; self.num_features];
+        for (r, out) in output.chunks_exact_mut(per_row.max(1)).enumerate() {
+            self.read_row(data, r, &mut row);
+            match predict_type {
+                predict_type::LEAF_INDEX => {
+                    for (dst, tree) in out.iter_mut().zip(&self.trees) {
+                        *dst = tree.leaf_index(&row) as f64;
+                    }
+                }
+                predict_type::CONTRIB => self.predict_contrib(&row, out),
+                _ => {
+                    self.predict_raw(&row, out);
+                    if predict_type == predict_type::NORMAL {
+                        self.convert_output(out);
+                    }
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Check the input matrix against the model and return the number of rows
+    fn check_shape(&self, len: usize, num_rows: i32, num_cols: i32) -> LightGBMResult<usize> {
+        let num_rows = usize::try_from(num_rows).map_err(|_| LightGBMError {
+            description: format!("Invalid number of rows: {}", num_rows),
+        })?;
+        if usize::try_from(num_cols).ok() != Some(self.num_features) {
+            return Err(LightGBMError {
+                description: format!(
+                    "The number of features in data ({}) is not the same as it was in training data ({})",
+                    num_cols, self.num_features
+                ),
+            });
+        }
+        if num_rows.checked_mul(self.num_features) != Some(len) {
+            return Err(LightGBMError {
+                description: format!(
+                    "Input data size mismatch: expected {}×{} elements, got {}",
+                    num_rows, num_cols, len
+                ),
+            });
+        }
+        Ok(num_rows)
+    }
+
+    /// Copy row `r` of the input, zeroing values as the C API does
+    fn read_row<T: Copy + Into<f64>>(&self, data: &[T], r: usize, row: &mut [f64]) {
+        let values = &data[r * self.num_features..(r + 1) * self.num_features];
+        for (dst, &value) in row.iter_mut().zip(values) {
+            *dst = to_feature_value(value.into());
+        }
+    }
+
+    /// Sum the leaf values of one row per class, iteration by iteration like LightGBM
+    fn predict_raw(&self, row: &[f64], out: &mut [f64]) {
+        out.fill(0.0);
+        for iteration in self.trees.chunks_exact(self.num_tree_per_iteration) {
+            for (score, tree) in out.iter_mut().zip(iteration) {
+                *score += tree.predict(row);
+            }
+        }
+    }
+
+    /// Turn the raw scores of one row into the model's output
+    fn convert_output(&self, out: &mut [f64]) {
+        if self.average_output {
+            let num_iterations = (self.trees.len() / self.num_tree_per_iteration).max(1);
+            for score in out.iter_mut() {
+                *score /= num_iterations as f64;
+            }
+        }
+        self.objective.transform(out);
+    }
+}
+
+impl NativeTree {
+    fn new(tree: &TreeModel, num_features: usize) -> LightGBMResult<Self> {
+        if tree.num_leaves == 0 {
+            return Err(LightGBMError {
+                description: "tree has no leaves".to_string(),
+            });
+        }
+
+        let num_internal = tree.num_internal();
+        let split_feature = tree
+            .split_feature
+            .iter()
+            .map(|&feature| {
+                usize::try_from(feature)
+                    .ok()
+                    .filter(|&f| f < num_features)
+                    .ok_or_else(|| LightGBMError {
+                        description: format!(
+                            "split feature {} out of range for {} features",
+                            feature, num_features
+                        ),
+                    })
+            })
+            .collect::<LightGBMResult<_>>()?;
+
+        // LightGBM numbers nodes in split order, so a child node always comes after its parent
+        for node in 0..num_internal {
+            for child in [tree.left_child[node], tree.right_child[node]] {
+                let valid = if child >= 0 {
+                    (child as usize) > node && (child as usize) < num_internal
+                } else {
+                    (!child as usize) < tree.num_leaves
+                };
+                if !valid {
+                    return Err(LightGBMError {
+                        description: format!("node {} has invalid child {}", node, child),
+                    });
+                }
+            }
+        }
+
+        let decision_type: Vec<u8> = tree.decision_type.iter().map(|&d| d as u8).collect();
+        let cat_boundaries = check_cat_boundaries(tree)?;
+        for (node, &decision) in decision_type.iter().enumerate() {
+            if decision & CATEGORICAL_MASK == 0 {
+                continue;
+            }
+            let cat_idx = tree.threshold[node];
+            if cat_idx.fract() != 0.0 || !(0.0..tree.num_cat as f64).contains(&cat_idx) {
+                return Err(LightGBMError {
+                    description: format!(
+                        "node {} refers to categorical split {} of {}",
+                        node, cat_idx, tree.num_cat
+                    ),
+                });
+            }
+        }
+
+        // Counts are only needed for SHAP values, which report their absence
+        let has_counts =
+            tree.leaf_count.len() == tree.num_leaves && tree.internal_count.len() == num_internal;
+        let counts = |values: &[i64]| -> Vec<f64> {
+            if has_counts {
+                values.iter().map(|&c| c as f64).collect()
+            } else {
+                Vec::new()
+            }
+        };
+
+        let mut depth = vec![0usize; num_internal];
+        let mut max_depth = 0;
+        for node in 0..num_internal {
+            for child in [tree.left_child[node], tree.right_child[node]] {
+                if child >= 0 {
+                    depth[child as usize] = depth[node] + 1;
+                } else {
+                    max_depth = max_depth.max(depth[node] + 1);
+                }
+            }
+        }
+
+        Ok(NativeTree {
+            split_feature,
+            threshold: tree.threshold.clone(),
+            decision_type,
+            left_child: tree.left_child.clone(),
+            right_child: tree.right_child.clone(),
+            leaf_value: tree.leaf_value.clone(),
+            cat_boundaries,
+            cat_threshold: tree.cat_threshold.clone(),
+            linear: if tree.is_linear {
+                Some(LinearLeaves::new(tree, num_features)?)
+            } else {
+                None
+            },
+            leaf_count: counts(&tree.leaf_count),
+            internal_count: counts(&tree.internal_count),
+            max_depth,
+        })
+    }
+
+    fn predict(&self, row: &[f64]) -> f64 {
+        let leaf = self.leaf_index(row);
+        match &self.linear {
+            Some(linear) => linear.predict(leaf, row).unwrap_or(self.leaf_value[leaf]),
+            None => self.leaf_value[leaf],
+        }
+    }
+
+    fn leaf_index(&self, row: &[f64]) -> usize {
+        if self.leaf_value.len() <= 1 {
+            return 0;
+        }
+        let mut node = 0i32;
+        while node >= 0 {
+            node = self.decision(row, node as usize);
+        }
+        !node as usize
+    }
+
+    /// Child to follow from an internal node
+    fn decision(&self, row: &[f64], node: usize) -> i32 {
+        let value = row[self.split_feature[node]];
+        let decision_type = self.decision_type[node];
+        let go_left = if decision_type & CATEGORICAL_MASK != 0 {
+            let cat_idx = self.threshold[node] as usize;
+            let bitset =
+                &self.cat_threshold[self.cat_boundaries[cat_idx]..self.cat_boundaries[cat_idx + 1]];
+            categorical_goes_left(bitset, value)
+        } else {
+            numerical_goes_left(decision_type, self.threshold[node], value)
+        };
+        if go_left {
+            self.left_child[node]
+        } else {
+            self.right_child[node]
+        }
+    }
+}
+
+/// As `Tree::NumericalDecision` in LightGBM
+#[inline]
+fn numerical_goes_left(decision_type: u8, threshold: f64, mut value: f64) -> bool {
+    let missing_type = missing_type(decision_type);
+    if value.is_nan() && missing_type != MISSING_TYPE_NAN {
+        value = 0.0;
+    }
+    let is_missing = match missing_type {
+        MISSING_TYPE_ZERO => is_zero(value),
+        MISSING_TYPE_NAN => value.is_nan(),
+        _ => false,
+    };
+    if is_missing {
+        decision_type & DEFAULT_LEFT_MASK != 0
+    } else {
+        value <= threshold
+    }
+}
+
+impl LinearLeaves {
+    fn new(tree: &TreeModel, num_features: usize) -> LightGBMResult<Self> {
+        let num_leaves = tree.num_leaves;
+        if tree.leaf_const.len() != num_leaves
+            || tree.leaf_features.len() != num_leaves
+            || tree.leaf_coeff.len() != num_leaves
+        {
+            return Err(LightGBMError {
+                description: format!(
+                    "linear leaves list {} constants, {} feature lists and {} coefficient lists for {} leaves",
+                    tree.leaf_const.len(),
+                    tree.leaf_features.len(),
+                    tree.leaf_coeff.len(),
+                    num_leaves
+                ),
+            });
+        }
+
+        let leaf_features = tree
+            .leaf_features
+            .iter()
+            .map(|features| {
+                features
+                    .iter()
+                    .map(|&feature| {
+                        usize::try_from(feature)
+                            .ok()
+                            .filter(|&f| f < num_features)
+                            .ok_or_else(|| LightGBMError {
+                                description: format!(
+                                    "linear leaf feature {} out of range for {} features",
+                                    feature, num_features
+                                ),
+                            })
+                    })
+                    .collect()
+            })
+            .collect::<LightGBMResult<_>>()?;
+
+        Ok(LinearLeaves {
+            leaf_const: tree.leaf_const.clone(),
+            leaf_features,
+            leaf_coeff: tree.leaf_coeff.clone(),
+        })
+    }
+
+    /// Output of a leaf's linear model, or `None` if any of its features is NaN, in which
+    /// case LightGBM uses the leaf's `leaf_value` instead
+    fn predict(&self, leaf: usize, row: &[f64]) -> Option<f64> {
+        let mut output = self.leaf_const[leaf];
+        for (&feature, &coeff) in self.leaf_features[leaf].iter().zip(&self.leaf_coeff[leaf]) {
+            let value = row[feature];
+            if value.is_nan() {
+                return None;
+            }
+            output += coeff * value;
+        }
+        Some(output)
+    }
+}
+
+/// Check that `cat_boundaries` holds `num_cat + 1` increasing offsets into `cat_threshold`
+fn check_cat_boundaries(tree: &TreeModel) -> LightGBMResult<Vec<usize>> {
+    if tree.num_cat == 0 {
+        return Ok(Vec::new());
+    }
+    let boundaries: Vec<usize> = tree
+        .cat_boundaries
+        .iter()
+        .map(|&b| usize::try_from(b).unwrap_or(usize::MAX))
+        .collect();
+    let valid = boundaries.len() == tree.num_cat + 1
+        && boundaries.windows(2).all(|w| w[0] <= w[1])
+        && boundaries
+            .last()
+            .is_some_and(|&end| end <= tree.cat_threshold.len());
+    if !valid {
+        return Err(LightGBMError {
+            description: format!(
+                "cat_boundaries {:?} do not match {} categorical splits over {} bitset words",
+                tree.cat_boundaries,
+                tree.num_cat,
+                tree.cat_threshold.len()
+            ),
+        });
+    }
+    Ok(boundaries)
+}
+
+/// The C API drops values LightGBM considers zero when reading dense rows
+fn to_feature_value(value: f64) -> f64 {
+    if is_zero(value) {
+        0.0
+    } else {
+        value
+    }
+}
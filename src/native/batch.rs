@@ -0,0 +1,595 @@
+use super::{numerical_goes_left, to_feature_value, NativeBooster, NativeTree};
+use crate::decision_type::{
+    categorical_goes_left, missing_type, CATEGORICAL_MASK, DEFAULT_LEFT_MASK, MISSING_TYPE_NAN,
+    MISSING_TYPE_ZERO,
+};
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::predict_type;
+use std::ops::Range;
+
+/// Rows converted together; traversed trees are applied to a whole block at a time
+const BLOCK_ROWS: usize = 256;
+
+/// Rows scored together by the bitvector pass, one per vector lane
+const LANES: usize = 16;
+
+/// Trees with more leaves than bits in a `u64` are traversed instead
+const MAX_BITVECTOR_LEAVES: usize = 64;
+
+/// Node flags of traversed trees, with the missing value handling of numerical splits
+/// resolved ahead of time
+const CATEGORICAL: u8 = 1;
+const DEFAULT_LEFT: u8 = 2;
+const NAN_MISSING: u8 = 4;
+const ZERO_MISSING: u8 = 8;
+/// NaN is read as zero by the split, and zero goes left
+const ZERO_LEFT: u8 = 16;
+
+/// A [`NativeBooster`] compiled for scoring large batches
+///
+/// Trees of up to 64 leaves are scored QuickScorer-style: each row keeps one `u64` per tree
+/// with a bit per leaf, in left-to-right order. A split that sends the row right clears the
+/// bits of its left subtree, and the exit leaf is the lowest bit left set once every false
+/// split has been applied. The outcome of a split does not depend on the tree it belongs to,
+/// so the numerical splits of the whole ensemble are grouped by feature and sorted by
+/// threshold: for each feature, a row only visits the splits whose threshold lies below its
+/// value, and stops at the first one that does not. Rows are scored sixteen at a time, one per
+/// vector lane, so each visited split updates sixteen bitvectors with a few vector compares and
+/// masks, using AVX2 when the CPU supports it. Zero and NaN inputs, which may take the default
+/// direction, use masks precomputed per feature; categorical splits are tested one by one.
+///
+/// Larger trees are traversed over flat node arrays, each tree applied to a block of rows at a
+/// time.
+///
+/// Results are identical to [`NativeBooster::predict`], as leaf values are summed in the same
+/// order.
+///
+/// # Example
+/// ```no_run
+/// use lightgbm_rust::{predict_type, NativeBooster};
+///
+/// let booster = NativeBooster::load("model.txt").unwrap();
+/// let predictor = booster.batch_predictor();
+///
+/// let data = vec![0.5f32; 1_000_000 * 4];
+/// let predictions = predictor
+///     .predict(&data, 1_000_000, 4, predict_type::NORMAL)
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct BatchPredictor {
+    booster: NativeBooster,
+    /// How each tree of the ensemble is scored
+    scoring: Vec<Scoring>,
+    bitvectors: Bitvectors,
+    traversed: FlatTrees,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Scoring {
+    /// Index of the tree's bitvector
+    Bitvector(usize),
+    /// Index of the tree among the traversed trees
+    Traversal(usize),
+}
+
+/// Trees scored with one bitvector per row
+#[derive(Clone, Debug, Default)]
+struct Bitvectors {
+    num_trees: usize,
+    /// Leaf index and output of each bit, `MAX_BITVECTOR_LEAVES` entries per tree
+    bit_leaf: Vec<u32>,
+    bit_value: Vec<f64>,
+    /// Numerical splits sorted by feature, then threshold; those of feature `f` are at
+    /// `feature_start[f]..feature_start[f + 1]`
+    feature_start: Vec<usize>,
+    threshold: Vec<f64>,
+    tree: Vec<u32>,
+    mask: Vec<u64>,
+    /// Splits sending a zero, respectively a NaN, value right
+    zero: MaskList,
+    nan: MaskList,
+    categorical: Vec<CategoricalSplit>,
+    cat_threshold: Vec<u32>,
+}
+
+/// Masks to apply for each feature
+#[derive(Clone, Debug, Default)]
+struct MaskList {
+    feature_start: Vec<usize>,
+    tree: Vec<u32>,
+    mask: Vec<u64>,
+}
+
+#[derive(Clone, Debug)]
+struct CategoricalSplit {
+    feature: usize,
+    tree: u32,
+    mask: u64,
+    /// Range of the bitset in `cat_threshold`
+    bitset: Range<usize>,
+}
+
+/// Traversed trees as flat arrays, with leaves as nodes that point back to themselves
+#[derive(Clone, Debug, Default)]
+struct FlatTrees {
+    feature: Vec<u32>,
+    threshold: Vec<f64>,
+    flags: Vec<u8>,
+    /// Left and right child of each node
+    children: Vec<[u32; 2]>,
+    /// Range of each categorical node's bitset in `cat_threshold`
+    cat_start: Vec<u32>,
+    cat_end: Vec<u32>,
+    cat_threshold: Vec<u32>,
+    /// Output and index within its tree of each leaf node
+    leaf_value: Vec<f64>,
+    leaf_index: Vec<u32>,
+    root: Vec<u32>,
+    depth: Vec<usize>,
+}
+
+impl NativeBooster {
+    /// Compile the model into a [`BatchPredictor`] for scoring large batches
+    pub fn batch_predictor(&self) -> BatchPredictor {
+        BatchPredictor::new(self)
+    }
+}
+
+impl BatchPredictor {
+    /// Compile a model into bitvector masks, and flat node arrays for larger trees
+    pub fn new(booster: &NativeBooster) -> Self {
+        let num_features = booster.num_features;
+        let mut bitvectors = Bitvectors::default();
+        let mut traversed = FlatTrees::default();
+        // (feature, threshold, tree, mask) of every numerical split
+        let mut numerical = Vec::new();
+        let mut zero = Vec::new();
+        let mut nan = Vec::new();
+
+        let scoring = booster
+            .trees
+            .iter()
+            .map(|tree| match bitvector_layout(tree) {
+                Some((leaves, splits)) => {
+                    let slot = bitvectors.num_trees;
+                    bitvectors.num_trees += 1;
+                    let id = slot as u32;
+                    for bit in 0..MAX_BITVECTOR_LEAVES {
+                        let leaf = leaves.get(bit).copied().unwrap_or(0);
+                        bitvectors.bit_leaf.push(leaf as u32);
+                        bitvectors.bit_value.push(tree.leaf_value[leaf]);
+                    }
+
+                    for (node, mask) in splits {
+                        let feature = tree.split_feature[node];
+                        let decision_type = tree.decision_type[node];
+                        let threshold = tree.threshold[node];
+                        if decision_type & CATEGORICAL_MASK != 0 {
+                            let cat_idx = threshold as usize;
+                            let bitset = &tree.cat_threshold
+                                [tree.cat_boundaries[cat_idx]..tree.cat_boundaries[cat_idx + 1]];
+                            let start = bitvectors.cat_threshold.len();
+                            bitvectors.cat_threshold.extend_from_slice(bitset);
+                            bitvectors.categorical.push(CategoricalSplit {
+                                feature,
+                                tree: id,
+                                mask,
+                                bitset: start..bitvectors.cat_threshold.len(),
+                            });
+                            continue;
+                        }
+                        numerical.push((feature, threshold, id, mask));
+                        if !numerical_goes_left(decision_type, threshold, 0.0) {
+                            zero.push((feature, id, mask));
+                        }
+                        if !numerical_goes_left(decision_type, threshold, f64::NAN) {
+                            nan.push((feature, id, mask));
+                        }
+                    }
+                    Scoring::Bitvector(slot)
+                }
+                None => {
+                    traversed.push(tree);
+                    Scoring::Traversal(traversed.root.len() - 1)
+                }
+            })
+            .collect();
+
+        numerical.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        bitvectors.feature_start = feature_starts(numerical.iter().map(|s| s.0), num_features);
+        bitvectors.threshold = numerical.iter().map(|s| s.1).collect();
+        bitvectors.tree = numerical.iter().map(|s| s.2).collect();
+        bitvectors.mask = numerical.iter().map(|s| s.3).collect();
+        bitvectors.zero = MaskList::new(zero, num_features);
+        bitvectors.nan = MaskList::new(nan, num_features);
+
+        BatchPredictor {
+            booster: booster.clone(),
+            scoring,
+            bitvectors,
+            traversed,
+        }
+    }
+
+    /// Predict for a dense row-major matrix
+    ///
+    /// Takes the same arguments and returns the same layout as [`NativeBooster::predict`].
+    /// Supported prediction types are [`NORMAL`](predict_type::NORMAL),
+    /// [`RAW_SCORE`](predict_type::RAW_SCORE) and [`LEAF_INDEX`](predict_type::LEAF_INDEX).
+    pub fn predict<T: Copy + Into<f64>>(
+        &self,
+        data: &[T],
+        num_rows: i32,
+        num_cols: i32,
+        predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        let booster = &self.booster;
+        let num_rows = booster.check_shape(data.len(), num_rows, num_cols)?;
+        let num_features = booster.num_features;
+        let num_trees = booster.trees.len();
+        let per_row = match predict_type {
+            predict_type::NORMAL | predict_type::RAW_SCORE => booster.num_tree_per_iteration,
+            predict_type::LEAF_INDEX => num_trees,
+            _ => {
+                return Err(LightGBMError {
+                    description: format!(
+                        "Prediction type {} is not supported by the batch predictor",
+                        predict_type
+                    ),
+                })
+            }
+        };
+
+        let mut output = vec![0.0f64; num_rows * per_row];
+        if num_rows == 0 || per_row == 0 {
+            return Ok(output);
+        }
+        let num_traversed = self.traversed.root.len();
+        let mut rows = vec![0.0f64; BLOCK_ROWS * num_features];
+        let mut cursor = vec![0u32; BLOCK_ROWS];
+        let mut leaf_nodes = vec![0u32; num_traversed * BLOCK_ROWS];
+        let mut lanes = vec![0.0f64; num_features * LANES];
+        let mut bits = vec![0u64; self.bitvectors.num_trees * LANES];
+
+        for (block, out) in output.chunks_mut(BLOCK_ROWS * per_row).enumerate() {
+            let block_rows = out.len() / per_row;
+            let first = block * BLOCK_ROWS * num_features;
+            let values = &data[first..first + block_rows * num_features];
+            for (dst, &value) in rows.iter_mut().zip(values) {
+                *dst = to_feature_value(value.into());
+            }
+            let rows = &rows[..block_rows * num_features];
+
+            for (j, nodes) in leaf_nodes.chunks_exact_mut(BLOCK_ROWS).enumerate() {
+                let cursor = &mut cursor[..block_rows];
+                self.traversed.traverse(j, num_features, rows, cursor);
+                nodes[..block_rows].copy_from_slice(cursor);
+            }
+
+            let groups = rows
+                .chunks(LANES * num_features)
+                .zip(out.chunks_mut(LANES * per_row));
+            for (g, (group, out)) in groups.enumerate() {
+                // Padding lanes hold -inf, which no split sends right
+                lanes.fill(f64::NEG_INFINITY);
+                for (lane, row) in group.chunks_exact(num_features).enumerate() {
+                    for (f, &value) in row.iter().enumerate() {
+                        lanes[f * LANES + lane] = value;
+                    }
+                }
+                self.bitvectors
+                    .score(&lanes, group, num_features, &mut bits);
+
+                for (lane, (row, out)) in group
+                    .chunks_exact(num_features)
+                    .zip(out.chunks_exact_mut(per_row))
+                    .enumerate()
+                {
+                    let row_in_block = g * LANES + lane;
+                    for (t, (tree, scoring)) in booster.trees.iter().zip(&self.scoring).enumerate()
+                    {
+                        let (leaf, value) = match *scoring {
+                            Scoring::Bitvector(slot) => {
+                                let bit = bits[slot * LANES + lane].trailing_zeros() as usize;
+                                let index = slot * MAX_BITVECTOR_LEAVES + bit;
+                                (
+                                    self.bitvectors.bit_leaf[index],
+                                    self.bitvectors.bit_value[index],
+                                )
+                            }
+                            Scoring::Traversal(j) => {
+                                let node = leaf_nodes[j * BLOCK_ROWS + row_in_block] as usize;
+                                (
+                                    self.traversed.leaf_index[node],
+                                    self.traversed.leaf_value[node],
+                                )
+                            }
+                        };
+
+                        if predict_type == predict_type::LEAF_INDEX {
+                            out[t] = leaf as f64;
+                            continue;
+                        }
+                        let value = match &tree.linear {
+                            Some(linear) => linear.predict(leaf as usize, row).unwrap_or(value),
+                            None => value,
+                        };
+                        out[t % booster.num_tree_per_iteration] += value;
+                    }
+
+                    if predict_type == predict_type::NORMAL {
+                        booster.convert_output(out);
+                    }
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl Bitvectors {
+    /// Compute the bitvectors of a group of up to `LANES` rows
+    ///
+    /// `lanes` holds the group's features transposed, `LANES` values per feature, and
+    /// `rows` the same rows in row-major order.
+    fn score(&self, lanes: &[f64], rows: &[f64], num_features: usize, bits: &mut [u64]) {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available on this CPU
+            return unsafe { self.score_avx2(lanes, rows, num_features, bits) };
+        }
+        self.score_lanes(lanes, rows, num_features, bits)
+    }
+
+    /// [`Bitvectors::score`] compiled for four `f64` per vector instead of two
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    unsafe fn score_avx2(
+        &self,
+        lanes: &[f64],
+        rows: &[f64],
+        num_features: usize,
+        bits: &mut [u64],
+    ) {
+        self.score_lanes(lanes, rows, num_features, bits)
+    }
+
+    #[inline(always)]
+    fn score_lanes(&self, lanes: &[f64], rows: &[f64], num_features: usize, bits: &mut [u64]) {
+        bits.fill(u64::MAX);
+
+        for f in 0..num_features {
+            let mut x = [0.0f64; LANES];
+            x.copy_from_slice(&lanes[f * LANES..(f + 1) * LANES]);
+            let mut max = f64::NEG_INFINITY;
+            for (lane, x) in x.iter_mut().enumerate() {
+                // Zero and NaN may take the default direction, so their masks are precomputed
+                if *x == 0.0 {
+                    self.zero.apply(f, lane, bits);
+                    *x = f64::NEG_INFINITY;
+                } else if x.is_nan() {
+                    self.nan.apply(f, lane, bits);
+                    *x = f64::NEG_INFINITY;
+                }
+                max = max.max(*x);
+            }
+
+            let splits = self.feature_start[f]..self.feature_start[f + 1];
+            for ((&threshold, &tree), &mask) in self.threshold[splits.clone()]
+                .iter()
+                .zip(&self.tree[splits.clone()])
+                .zip(&self.mask[splits])
+            {
+                // Splits are sorted by threshold, none of the remaining ones sends a row right
+                if threshold >= max {
+                    break;
+                }
+                let start = tree as usize * LANES;
+                for (bits, &x) in bits[start..start + LANES].iter_mut().zip(&x) {
+                    *bits &= if x > threshold { mask } else { u64::MAX };
+                }
+            }
+        }
+
+        for split in &self.categorical {
+            let bitset = &self.cat_threshold[split.bitset.clone()];
+            for (lane, row) in rows.chunks_exact(num_features).enumerate() {
+                if !categorical_goes_left(bitset, row[split.feature]) {
+                    bits[split.tree as usize * LANES + lane] &= split.mask;
+                }
+            }
+        }
+    }
+}
+
+impl MaskList {
+    /// Group `(feature, tree, mask)` entries by feature
+    fn new(mut entries: Vec<(usize, u32, u64)>, num_features: usize) -> Self {
+        entries.sort_by_key(|e| e.0);
+        MaskList {
+            feature_start: feature_starts(entries.iter().map(|e| e.0), num_features),
+            tree: entries.iter().map(|e| e.1).collect(),
+            mask: entries.iter().map(|e| e.2).collect(),
+        }
+    }
+
+    #[inline]
+    fn apply(&self, feature: usize, lane: usize, bits: &mut [u64]) {
+        let entries = self.feature_start[feature]..self.feature_start[feature + 1];
+        for (&tree, &mask) in self.tree[entries.clone()].iter().zip(&self.mask[entries]) {
+            bits[tree as usize * LANES + lane] &= mask;
+        }
+    }
+}
+
+impl FlatTrees {
+    fn push(&mut self, tree: &NativeTree) {
+        let base = self.feature.len() as u32;
+        let num_internal = tree.leaf_value.len() - 1;
+        let node_id = |child: i32| -> u32 {
+            if child >= 0 {
+                base + child as u32
+            } else {
+                base + num_internal as u32 + !child as u32
+            }
+        };
+        let cat_base = self.cat_threshold.len() as u32;
+        self.cat_threshold.extend_from_slice(&tree.cat_threshold);
+
+        for node in 0..num_internal {
+            let decision_type = tree.decision_type[node];
+            self.feature.push(tree.split_feature[node] as u32);
+            self.threshold.push(tree.threshold[node]);
+            self.flags
+                .push(node_flags(decision_type, tree.threshold[node]));
+            self.children.push([
+                node_id(tree.left_child[node]),
+                node_id(tree.right_child[node]),
+            ]);
+            if decision_type & CATEGORICAL_MASK != 0 {
+                let cat_idx = tree.threshold[node] as usize;
+                self.cat_start
+                    .push(cat_base + tree.cat_boundaries[cat_idx] as u32);
+                self.cat_end
+                    .push(cat_base + tree.cat_boundaries[cat_idx + 1] as u32);
+            } else {
+                self.cat_start.push(0);
+                self.cat_end.push(0);
+            }
+            self.leaf_value.push(0.0);
+            self.leaf_index.push(0);
+        }
+        for (leaf, &value) in tree.leaf_value.iter().enumerate() {
+            let id = self.feature.len() as u32;
+            // Any step from a leaf stays on the leaf
+            self.feature.push(0);
+            self.threshold.push(0.0);
+            self.flags.push(0);
+            self.children.push([id, id]);
+            self.cat_start.push(0);
+            self.cat_end.push(0);
+            self.leaf_value.push(value);
+            self.leaf_index.push(leaf as u32);
+        }
+
+        self.root
+            .push(if num_internal > 0 { base } else { node_id(-1) });
+        self.depth.push(tree.max_depth);
+    }
+
+    /// Move every row of a block from the root of traversed tree `t` to its leaf
+    fn traverse(&self, t: usize, num_features: usize, rows: &[f64], cursor: &mut [u32]) {
+        cursor.fill(self.root[t]);
+        for _ in 0..self.depth[t] {
+            for (r, node) in cursor.iter_mut().enumerate() {
+                *node = self.step(*node, &rows[r * num_features..(r + 1) * num_features]);
+            }
+        }
+    }
+
+    #[inline]
+    fn step(&self, node: u32, row: &[f64]) -> u32 {
+        let n = node as usize;
+        let value = row[self.feature[n] as usize];
+        let flags = self.flags[n];
+        let go_left = if flags & CATEGORICAL != 0 {
+            let bitset = &self.cat_threshold[self.cat_start[n] as usize..self.cat_end[n] as usize];
+            categorical_goes_left(bitset, value)
+        } else {
+            // Same outcome as `numerical_goes_left`, as rows only hold exact zeros
+            let nan = value.is_nan();
+            let missing = (nan & (flags & NAN_MISSING != 0))
+                | ((nan | (value == 0.0)) & (flags & ZERO_MISSING != 0));
+            let below = (value <= self.threshold[n]) | (nan & (flags & ZERO_LEFT != 0));
+            (missing & (flags & DEFAULT_LEFT != 0)) | (!missing & below)
+        };
+        self.children[n][!go_left as usize]
+    }
+}
+
+/// Node index of each split, and the mask applied when it sends a row right
+type SplitMasks = Vec<(usize, u64)>;
+
+/// Leaves of a tree in left-to-right order, and the reachable splits with the mask clearing
+/// the bits of their left subtree
+///
+/// `None` if the tree has too many leaves for a `u64`, a numerical threshold is NaN (sorting
+/// needs an order), or a node is reachable along two paths.
+fn bitvector_layout(tree: &NativeTree) -> Option<(Vec<usize>, SplitMasks)> {
+    let num_leaves = tree.leaf_value.len();
+    if num_leaves > MAX_BITVECTOR_LEAVES {
+        return None;
+    }
+    if num_leaves == 1 {
+        return Some((vec![0], Vec::new()));
+    }
+
+    let mut leaves = Vec::with_capacity(num_leaves);
+    let mut splits = Vec::with_capacity(num_leaves - 1);
+    let mut visited = vec![false; num_leaves - 1];
+    layout_node(tree, 0, &mut leaves, &mut splits, &mut visited)?;
+    Some((leaves, splits))
+}
+
+/// Lay out the subtree below `node`, returning the bits of its leaves
+fn layout_node(
+    tree: &NativeTree,
+    node: i32,
+    leaves: &mut Vec<usize>,
+    splits: &mut SplitMasks,
+    visited: &mut [bool],
+) -> Option<Range<usize>> {
+    if node < 0 {
+        let leaf = !node as usize;
+        if leaves.contains(&leaf) {
+            return None;
+        }
+        leaves.push(leaf);
+        return Some(leaves.len() - 1..leaves.len());
+    }
+
+    let n = node as usize;
+    let numerical = tree.decision_type[n] & CATEGORICAL_MASK == 0;
+    if std::mem::replace(&mut visited[n], true) || (numerical && tree.threshold[n].is_nan()) {
+        return None;
+    }
+    let left = layout_node(tree, tree.left_child[n], leaves, splits, visited)?;
+    let right = layout_node(tree, tree.right_child[n], leaves, splits, visited)?;
+    // The right subtree holds at least one leaf, so the left one spans at most 63 bits
+    let left_bits = ((1u64 << left.len()) - 1) << left.start;
+    splits.push((n, !left_bits));
+    Some(left.start..right.end)
+}
+
+/// Start of each feature's entries, given the feature of every entry in sorted order
+fn feature_starts(features: impl Iterator<Item = usize>, num_features: usize) -> Vec<usize> {
+    let mut starts = vec![0usize; num_features + 1];
+    for feature in features {
+        starts[feature + 1] += 1;
+    }
+    for f in 0..num_features {
+        starts[f + 1] += starts[f];
+    }
+    starts
+}
+
+fn node_flags(decision_type: u8, threshold: f64) -> u8 {
+    if decision_type & CATEGORICAL_MASK != 0 {
+        return CATEGORICAL;
+    }
+    let mut flags = 0;
+    if decision_type & DEFAULT_LEFT_MASK != 0 {
+        flags |= DEFAULT_LEFT;
+    }
+    match missing_type(decision_type) {
+        MISSING_TYPE_NAN => flags |= NAN_MISSING,
+        MISSING_TYPE_ZERO => flags |= ZERO_MISSING,
+        _ => {}
+    }
+    if 0.0 <= threshold {
+        flags |= ZERO_LEFT;
+    }
+    flags
+}
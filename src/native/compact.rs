@@ -0,0 +1,92 @@
+use super::NativeBooster;
+use crate::decision_type::CATEGORICAL_MASK;
+use crate::embedded::{MAGIC, VERSION};
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model_format::threshold_to_f32;
+
+impl NativeBooster {
+    /// Convert the model to the compact binary format read by
+    /// [`CompactBooster`](crate::CompactBooster)
+    ///
+    /// Linear trees are not supported.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::NativeBooster;
+    ///
+    /// let booster = NativeBooster::load("model.txt").unwrap();
+    /// std::fs::write("model.lgbc", booster.to_compact().unwrap()).unwrap();
+    /// ```
+    pub fn to_compact(&self) -> LightGBMResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        put_u32(&mut bytes, self.num_features)?;
+        put_u32(&mut bytes, self.num_tree_per_iteration)?;
+        bytes.push(self.average_output as u8);
+        let (tag, param) = self.objective.to_tag();
+        bytes.push(tag);
+        bytes.extend_from_slice(&param.to_le_bytes());
+        put_u32(&mut bytes, self.trees.len())?;
+
+        // Bitsets of all trees, renumbered so that each split refers to a global index
+        let mut bitset_base = Vec::with_capacity(self.trees.len());
+        let mut boundaries = vec![0usize];
+        let mut words = Vec::new();
+        for tree in &self.trees {
+            bitset_base.push(boundaries.len() - 1);
+            if let (Some(&first), Some(&last)) =
+                (tree.cat_boundaries.first(), tree.cat_boundaries.last())
+            {
+                let base = words.len();
+                boundaries.extend(tree.cat_boundaries[1..].iter().map(|&b| base + b - first));
+                words.extend_from_slice(&tree.cat_threshold[first..last]);
+            }
+        }
+        put_u32(&mut bytes, boundaries.len() - 1)?;
+        for &boundary in &boundaries {
+            put_u32(&mut bytes, boundary)?;
+        }
+        for word in words {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+
+        for (t, tree) in self.trees.iter().enumerate() {
+            if tree.linear.is_some() {
+                return Err(LightGBMError {
+                    description: format!(
+                        "Tree {}: linear trees are not supported by the compact format",
+                        t
+                    ),
+                });
+            }
+            put_u32(&mut bytes, tree.leaf_value.len())?;
+            for node in 0..tree.leaf_value.len() - 1 {
+                let decision_type = tree.decision_type[node];
+                let threshold = if decision_type & CATEGORICAL_MASK != 0 {
+                    (bitset_base[t] + tree.threshold[node] as usize) as f32
+                } else {
+                    threshold_to_f32(tree.threshold[node])
+                };
+                put_u32(&mut bytes, tree.split_feature[node])?;
+                bytes.push(decision_type);
+                bytes.extend_from_slice(&threshold.to_le_bytes());
+                bytes.extend_from_slice(&tree.left_child[node].to_le_bytes());
+                bytes.extend_from_slice(&tree.right_child[node].to_le_bytes());
+            }
+            for &value in &tree.leaf_value {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        Ok(bytes)
+    }
+}
+
+fn put_u32(bytes: &mut Vec<u8>, value: usize) -> LightGBMResult<()> {
+    let value = u32::try_from(value).map_err(|_| LightGBMError {
+        description: format!("{} does not fit the compact format", value),
+    })?;
+    bytes.extend_from_slice(&value.to_le_bytes());
+    Ok(())
+}
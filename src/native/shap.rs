@@ -0,0 +1,375 @@
+//! TreeSHAP (Lundberg et al., "Consistent Individualized Feature Attribution for Tree
+//! Ensembles"), following `Tree::TreeSHAP` in LightGBM, extended with the conditioning used
+//! for SHAP interaction values.
+
+use super::{NativeBooster, NativeTree};
+use crate::error::{LightGBMError, LightGBMResult};
+
+/// One feature on the path from the root to the current node
+#[derive(Clone, Copy, Debug, Default)]
+struct PathElement {
+    feature_index: i32,
+    zero_fraction: f64,
+    one_fraction: f64,
+    pweight: f64,
+}
+
+/// How the recursion treats one feature when computing interaction values
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Condition {
+    /// Plain SHAP values
+    None,
+    /// SHAP values with the feature fixed to its value in the row
+    On(usize),
+    /// SHAP values with the feature marginalized out
+    Off(usize),
+}
+
+impl Condition {
+    fn feature(self) -> Option<usize> {
+        match self {
+            Condition::None => None,
+            Condition::On(feature) | Condition::Off(feature) => Some(feature),
+        }
+    }
+}
+
+impl NativeBooster {
+    /// Compute SHAP interaction values for a dense row-major matrix
+    ///
+    /// For each row and class, returns a `(num_features + 1) × (num_features + 1)` matrix in
+    /// row-major order: entry `(i, j)` is half the interaction effect between features `i`
+    /// and `j`, the diagonal holds each feature's main effect, and the last diagonal entry is
+    /// the expected value. Each matrix row sums to the feature's SHAP value as returned by
+    /// [`predict_type::CONTRIB`](crate::predict_type::CONTRIB), and the whole matrix sums to
+    /// the raw score.
+    ///
+    /// The output has `num_rows × classes × (num_features + 1)²` values.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::NativeBooster;
+    ///
+    /// let booster = NativeBooster::load("model.txt").unwrap();
+    /// let data = vec![1.0f64, 2.0, 3.0, 4.0];
+    /// let interactions = booster.predict_interactions(&data, 1, 4).unwrap();
+    /// // Interaction between features 0 and 1
+    /// let width = 5;
+    /// println!("{}", interactions[width] + interactions[1]);
+    /// ```
+    pub fn predict_interactions<T: Copy + Into<f64>>(
+        &self,
+        data: &[T],
+        num_rows: i32,
+        num_cols: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        let num_rows = self.check_shape(data.len(), num_rows, num_cols)?;
+        self.check_shap_support()?;
+
+        let width = self.num_features + 1;
+        let per_class = width * width;
+        let mut output = vec![0.0f64; num_rows * self.num_tree_per_iteration * per_class];
+        let mut row = vec![0.0f64; self.num_features];
+        let mut scratch = vec![0.0f64; 3 * width];
+        for (r, out) in output
+            .chunks_exact_mut(self.num_tree_per_iteration * per_class)
+            .enumerate()
+        {
+            self.read_row(data, r, &mut row);
+            for iteration in self.trees.chunks_exact(self.num_tree_per_iteration) {
+                for (class_out, tree) in out.chunks_exact_mut(per_class).zip(iteration) {
+                    tree.interactions(&row, class_out, &mut scratch);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// SHAP values need per-node sample counts, and do not apply to linear trees
+    pub(super) fn check_shap_support(&self) -> LightGBMResult<()> {
+        for (i, tree) in self.trees.iter().enumerate() {
+            if tree.linear.is_some() {
+                return Err(LightGBMError {
+                    description: format!("SHAP values are not supported for linear tree {}", i),
+                });
+            }
+            if tree.leaf_value.len() > 1 && tree.leaf_count.len() != tree.leaf_value.len() {
+                return Err(LightGBMError {
+                    description: format!(
+                        "Tree {} lacks the leaf_count and internal_count needed for SHAP values",
+                        i
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// SHAP values of one row, `num_features + 1` per class, as `GBDT::PredictContrib`
+    pub(super) fn predict_contrib(&self, row: &[f64], out: &mut [f64]) {
+        out.fill(0.0);
+        let width = self.num_features + 1;
+        for iteration in self.trees.chunks_exact(self.num_tree_per_iteration) {
+            for (phi, tree) in out.chunks_exact_mut(width).zip(iteration) {
+                tree.contrib(row, phi);
+            }
+        }
+    }
+}
+
+impl NativeTree {
+    /// Mean output over the training samples, as `Tree::ExpectedValue`
+    fn expected_value(&self) -> f64 {
+        if self.leaf_value.len() == 1 {
+            return self.leaf_value[0];
+        }
+        let total_count = self.internal_count[0];
+        self.leaf_value
+            .iter()
+            .zip(&self.leaf_count)
+            .fold(0.0, |sum, (value, count)| {
+                sum + (count / total_count) * value
+            })
+    }
+
+    fn data_count(&self, node: i32) -> f64 {
+        if node >= 0 {
+            self.internal_count[node as usize]
+        } else {
+            self.leaf_count[!node as usize]
+        }
+    }
+
+    /// Add the SHAP values of one row to `phi`, which has `num_features + 1` entries
+    fn contrib(&self, row: &[f64], phi: &mut [f64]) {
+        let bias = phi.len() - 1;
+        phi[bias] += self.expected_value();
+        if self.leaf_value.len() > 1 {
+            self.shap(row, phi, Condition::None);
+        }
+    }
+
+    /// Add the SHAP interaction values of one row to the row-major matrix `out`
+    ///
+    /// `scratch` holds at least `3 × (num_features + 1)` values.
+    fn interactions(&self, row: &[f64], out: &mut [f64], scratch: &mut [f64]) {
+        let width = row.len() + 1;
+        let (phi, rest) = scratch.split_at_mut(width);
+        let (on, rest) = rest.split_at_mut(width);
+        let off = &mut rest[..width];
+
+        phi.fill(0.0);
+        self.contrib(row, phi);
+        for (i, &value) in phi.iter().enumerate() {
+            out[i * width + i] += value;
+        }
+
+        // Features the tree does not split on interact with nothing
+        let mut features = self.split_feature.clone();
+        features.sort_unstable();
+        features.dedup();
+        for feature in features {
+            on.fill(0.0);
+            off.fill(0.0);
+            self.shap(row, on, Condition::On(feature));
+            self.shap(row, off, Condition::Off(feature));
+
+            let out_row = &mut out[feature * width..(feature + 1) * width];
+            for k in 0..width {
+                if k != feature {
+                    let interaction = (on[k] - off[k]) / 2.0;
+                    out_row[k] += interaction;
+                    out_row[feature] -= interaction;
+                }
+            }
+        }
+    }
+
+    fn shap(&self, row: &[f64], phi: &mut [f64], condition: Condition) {
+        let max_path_len = self.max_depth + 2;
+        let mut path = vec![PathElement::default(); max_path_len * (max_path_len + 1) / 2];
+        self.tree_shap(row, phi, &mut path, 0, 0, 1.0, 1.0, -1, condition, 1.0);
+    }
+
+    /// Recurse into `node`, where `path` starts with the parent's path
+    #[allow(clippy::too_many_arguments)]
+    fn tree_shap(
+        &self,
+        row: &[f64],
+        phi: &mut [f64],
+        path: &mut [PathElement],
+        node: i32,
+        unique_depth: usize,
+        parent_zero_fraction: f64,
+        parent_one_fraction: f64,
+        parent_feature_index: i32,
+        condition: Condition,
+        condition_fraction: f64,
+    ) {
+        if condition_fraction == 0.0 {
+            return;
+        }
+
+        // Extend a copy of the parent's path
+        let (parent_path, path) = path.split_at_mut(unique_depth + 1);
+        path[..unique_depth + 1].copy_from_slice(parent_path);
+        if condition.feature().map(|f| f as i32) != Some(parent_feature_index) {
+            extend_path(
+                path,
+                unique_depth,
+                parent_zero_fraction,
+                parent_one_fraction,
+                parent_feature_index,
+            );
+        }
+
+        if node < 0 {
+            let leaf_value = self.leaf_value[!node as usize];
+            for i in 1..=unique_depth {
+                let w = unwound_path_sum(path, unique_depth, i);
+                let element = path[i];
+                phi[element.feature_index as usize] += w
+                    * (element.one_fraction - element.zero_fraction)
+                    * leaf_value
+                    * condition_fraction;
+            }
+            return;
+        }
+
+        let index = node as usize;
+        let hot_index = self.decision(row, index);
+        let cold_index = if hot_index == self.left_child[index] {
+            self.right_child[index]
+        } else {
+            self.left_child[index]
+        };
+        let w = self.data_count(node);
+        let hot_zero_fraction = self.data_count(hot_index) / w;
+        let cold_zero_fraction = self.data_count(cold_index) / w;
+        let mut incoming_zero_fraction = 1.0;
+        let mut incoming_one_fraction = 1.0;
+        let split_feature = self.split_feature[index];
+        let mut depth = unique_depth as isize;
+
+        // If the feature was already split on, undo that split so it can be redone here
+        if let Some(path_index) = path[..=unique_depth]
+            .iter()
+            .position(|element| element.feature_index == split_feature as i32)
+        {
+            incoming_zero_fraction = path[path_index].zero_fraction;
+            incoming_one_fraction = path[path_index].one_fraction;
+            unwind_path(path, unique_depth, path_index);
+            depth -= 1;
+        }
+
+        // Divide the condition fraction between the branches
+        let mut hot_condition_fraction = condition_fraction;
+        let mut cold_condition_fraction = condition_fraction;
+        match condition {
+            Condition::On(feature) if feature == split_feature => {
+                cold_condition_fraction = 0.0;
+                depth -= 1;
+            }
+            Condition::Off(feature) if feature == split_feature => {
+                hot_condition_fraction *= hot_zero_fraction;
+                cold_condition_fraction *= cold_zero_fraction;
+                depth -= 1;
+            }
+            _ => {}
+        }
+
+        let child_depth = (depth + 1) as usize;
+        self.tree_shap(
+            row,
+            phi,
+            path,
+            hot_index,
+            child_depth,
+            hot_zero_fraction * incoming_zero_fraction,
+            incoming_one_fraction,
+            split_feature as i32,
+            condition,
+            hot_condition_fraction,
+        );
+        self.tree_shap(
+            row,
+            phi,
+            path,
+            cold_index,
+            child_depth,
+            cold_zero_fraction * incoming_zero_fraction,
+            0.0,
+            split_feature as i32,
+            condition,
+            cold_condition_fraction,
+        );
+    }
+}
+
+fn extend_path(
+    path: &mut [PathElement],
+    unique_depth: usize,
+    zero_fraction: f64,
+    one_fraction: f64,
+    feature_index: i32,
+) {
+    path[unique_depth] = PathElement {
+        feature_index,
+        zero_fraction,
+        one_fraction,
+        pweight: if unique_depth == 0 { 1.0 } else { 0.0 },
+    };
+    let depth = unique_depth as f64;
+    for i in (0..unique_depth).rev() {
+        path[i + 1].pweight += one_fraction * path[i].pweight * (i + 1) as f64 / (depth + 1.0);
+        path[i].pweight =
+            zero_fraction * path[i].pweight * (unique_depth - i) as f64 / (depth + 1.0);
+    }
+}
+
+fn unwind_path(path: &mut [PathElement], unique_depth: usize, path_index: usize) {
+    let one_fraction = path[path_index].one_fraction;
+    let zero_fraction = path[path_index].zero_fraction;
+    let depth = unique_depth as f64;
+    let mut next_one_portion = path[unique_depth].pweight;
+
+    for i in (0..unique_depth).rev() {
+        if one_fraction != 0.0 {
+            let tmp = path[i].pweight;
+            path[i].pweight = next_one_portion * (depth + 1.0) / ((i + 1) as f64 * one_fraction);
+            next_one_portion =
+                tmp - path[i].pweight * zero_fraction * (unique_depth - i) as f64 / (depth + 1.0);
+        } else {
+            path[i].pweight =
+                (path[i].pweight * (depth + 1.0)) / (zero_fraction * (unique_depth - i) as f64);
+        }
+    }
+
+    for i in path_index..unique_depth {
+        path[i].feature_index = path[i + 1].feature_index;
+        path[i].zero_fraction = path[i + 1].zero_fraction;
+        path[i].one_fraction = path[i + 1].one_fraction;
+    }
+}
+
+/// Total path weight if the feature at `path_index` were removed from the path
+fn unwound_path_sum(path: &[PathElement], unique_depth: usize, path_index: usize) -> f64 {
+    let one_fraction = path[path_index].one_fraction;
+    let zero_fraction = path[path_index].zero_fraction;
+    let mut next_one_portion = path[unique_depth].pweight;
+    let mut total = 0.0;
+
+    if one_fraction != 0.0 {
+        for i in (0..unique_depth).rev() {
+            let tmp = next_one_portion / ((i + 1) as f64 * one_fraction);
+            total += tmp;
+            next_one_portion = path[i].pweight - tmp * zero_fraction * (unique_depth - i) as f64;
+        }
+    } else {
+        for i in (0..unique_depth).rev() {
+            total += path[i].pweight / (zero_fraction * (unique_depth - i) as f64);
+        }
+    }
+    total * (unique_depth as f64 + 1.0)
+}
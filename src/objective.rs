@@ -0,0 +1,234 @@
+//! Output transforms of LightGBM objectives
+//!
+//! LightGBM turns the raw scores of a model (the sum of its trees) into predictions with the
+//! transform of the objective it was trained with, named on the `objective=` line of the model
+//! header. [`Objective`] reproduces these transforms in Rust, e.g. to convert
+//! [`RAW_SCORE`](crate::predict_type::RAW_SCORE) outputs received from elsewhere.
+//!
+//! # Example
+//! ```
+//! use lightgbm_rust::objective::Objective;
+//!
+//! let objective: Objective = "multiclass num_class:3".parse().unwrap();
+//!
+//! // Raw scores of two rows, one per class
+//! let mut scores = vec![1.0, 2.0, 0.5, -1.0, 0.0, 3.0];
+//! objective.transform_predictions(&mut scores, 3).unwrap();
+//! assert!((scores[..3].iter().sum::<f64>() - 1.0).abs() < 1e-12);
+//! ```
+
+use crate::error::{LightGBMError, LightGBMResult};
+#[cfg(feature = "ffi")]
+use crate::model::{Booster, SaveOptions};
+use alloc::format;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+/// `f64` functions from `std`, or from `libm` in `no_std` builds
+#[cfg(feature = "std")]
+mod math {
+    pub(super) fn exp(x: f64) -> f64 {
+        x.exp()
+    }
+
+    pub(super) fn ln_1p(x: f64) -> f64 {
+        x.ln_1p()
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod math {
+    pub(super) use libm::{exp, log1p as ln_1p};
+}
+
+/// Output transform of a LightGBM objective, turning raw scores into predictions
+///
+/// Mirrors `ObjectiveFunction::ConvertOutput` in LightGBM for each built-in objective.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Objective {
+    /// Raw score is the prediction (regression losses, ranking, custom objectives)
+    Identity,
+    /// Regression trained on `sqrt` of the label: `sign(x) * x²`
+    Square,
+    /// Log-link regression (poisson, gamma, tweedie): `exp(x)`
+    Exp,
+    /// Binary classification: `1 / (1 + exp(-sigmoid * x))`
+    Sigmoid(f64),
+    /// Multiclass classification: softmax over the class scores
+    Softmax,
+    /// One-vs-all multiclass classification: a sigmoid per class
+    SigmoidPerClass(f64),
+    /// Cross-entropy: `1 / (1 + exp(-x))`
+    CrossEntropy,
+    /// Cross-entropy with the lambda parameterization: `log(1 + exp(x))`
+    CrossEntropyLambda,
+}
+
+impl Objective {
+    /// Parse the `objective=` header value of a text model, e.g. `binary sigmoid:1`
+    ///
+    /// A model without an objective line (trained with a custom objective) has no transform.
+    /// Regression losses (`regression`, `regression_l1`, `huber`, `fair`, `quantile`, `mape`)
+    /// and ranking objectives (`lambdarank`, `rank_xendcg`) output raw scores, unless the
+    /// model was trained with `reg_sqrt`.
+    pub fn parse(objective: Option<&str>) -> LightGBMResult<Self> {
+        let Some(objective) = objective else {
+            return Ok(Objective::Identity);
+        };
+        let mut tokens = objective.split_whitespace();
+        let name = tokens.next().unwrap_or("");
+        let params: Vec<&str> = tokens.collect();
+        let sigmoid = || -> LightGBMResult<f64> {
+            params
+                .iter()
+                .find_map(|p| p.strip_prefix("sigmoid:"))
+                .map_or(Ok(1.0), |value| {
+                    value.parse::<f64>().map_err(|e| LightGBMError {
+                        description: format!("Invalid sigmoid in objective '{}': {}", objective, e),
+                    })
+                })
+        };
+
+        Ok(match name {
+            "regression" | "regression_l1" | "huber" | "fair" | "quantile" | "mape" => {
+                if params.contains(&"sqrt") {
+                    Objective::Square
+                } else {
+                    Objective::Identity
+                }
+            }
+            "poisson" | "gamma" | "tweedie" => Objective::Exp,
+            "binary" => Objective::Sigmoid(sigmoid()?),
+            "multiclass" => Objective::Softmax,
+            "multiclassova" => Objective::SigmoidPerClass(sigmoid()?),
+            "cross_entropy" => Objective::CrossEntropy,
+            "cross_entropy_lambda" => Objective::CrossEntropyLambda,
+            "lambdarank" | "rank_xendcg" | "custom" => Objective::Identity,
+            _ => {
+                return Err(LightGBMError {
+                    description: format!("Unsupported objective '{}'", objective),
+                })
+            }
+        })
+    }
+
+    /// Transform the raw scores of one row in place
+    ///
+    /// `scores` holds one value per tree of an iteration, i.e. one per class for multiclass.
+    pub fn transform(&self, scores: &mut [f64]) {
+        match *self {
+            Objective::Identity => {}
+            Objective::Square => {
+                for x in scores {
+                    *x = if x.is_sign_negative() {
+                        -(*x * *x)
+                    } else {
+                        *x * *x
+                    };
+                }
+            }
+            Objective::Exp => {
+                for x in scores {
+                    *x = math::exp(*x);
+                }
+            }
+            Objective::Sigmoid(sigmoid) | Objective::SigmoidPerClass(sigmoid) => {
+                for x in scores {
+                    *x = 1.0 / (1.0 + math::exp(-sigmoid * *x));
+                }
+            }
+            Objective::Softmax => {
+                let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let mut sum = 0.0;
+                for x in scores.iter_mut() {
+                    *x = math::exp(*x - max);
+                    sum += *x;
+                }
+                for x in scores {
+                    *x /= sum;
+                }
+            }
+            Objective::CrossEntropy => {
+                for x in scores {
+                    *x = 1.0 / (1.0 + math::exp(-*x));
+                }
+            }
+            Objective::CrossEntropyLambda => {
+                // Softplus as `max(x, 0) + log(1 + exp(-|x|))`, which does not overflow
+                for x in scores {
+                    *x = if *x > 0.0 {
+                        *x + math::ln_1p(math::exp(-*x))
+                    } else {
+                        math::ln_1p(math::exp(*x))
+                    };
+                }
+            }
+        }
+    }
+
+    /// Transform the raw scores of many rows in place
+    ///
+    /// `predictions` is laid out like the output of
+    /// [`Booster::predict`](crate::Booster::predict) with
+    /// [`RAW_SCORE`](crate::predict_type::RAW_SCORE): `num_outputs` scores per row, where
+    /// `num_outputs` is the number of trees per iteration (the number of classes for multiclass
+    /// models, else 1).
+    ///
+    /// Models with `average_output` (random forests) divide raw scores by the number of
+    /// iterations before the transform; do so first to reproduce their normal predictions.
+    pub fn transform_predictions(
+        &self,
+        predictions: &mut [f64],
+        num_outputs: usize,
+    ) -> LightGBMResult<()> {
+        if num_outputs == 0 || predictions.len() % num_outputs != 0 {
+            return Err(LightGBMError {
+                description: format!(
+                    "{} predictions are not a whole number of rows of {} outputs",
+                    predictions.len(),
+                    num_outputs
+                ),
+            });
+        }
+        for scores in predictions.chunks_exact_mut(num_outputs) {
+            self.transform(scores);
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Objective {
+    type Err = LightGBMError;
+
+    /// Parse an objective as written in a model header, e.g. `binary sigmoid:1`
+    fn from_str(objective: &str) -> LightGBMResult<Self> {
+        Objective::parse(Some(objective))
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl Booster {
+    /// Get the output transform of the model's objective
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::{predict_type, Booster};
+    ///
+    /// let booster = Booster::load("model.txt").unwrap();
+    /// let data = vec![1.0f64, 2.0, 3.0, 4.0];
+    /// let mut scores = booster.predict(&data, 1, 4, predict_type::RAW_SCORE).unwrap();
+    ///
+    /// let num_outputs = booster.num_model_per_iteration().unwrap() as usize;
+    /// let objective = booster.objective().unwrap();
+    /// objective.transform_predictions(&mut scores, num_outputs).unwrap();
+    /// ```
+    pub fn objective(&self) -> LightGBMResult<Objective> {
+        // The header is all that is needed, so skip writing most trees
+        let text = self.save_model_to_string(&SaveOptions::default().with_num_iteration(1))?;
+        let objective = text
+            .lines()
+            .take_while(|line| !line.starts_with("Tree="))
+            .find_map(|line| line.strip_prefix("objective="));
+        Objective::parse(objective)
+    }
+}
@@ -0,0 +1,655 @@
+use crate::decision_type::{
+    missing_type, CATEGORICAL_MASK, DEFAULT_LEFT_MASK, MISSING_TYPE_NAN, MISSING_TYPE_ZERO,
+    ZERO_THRESHOLD,
+};
+use crate::error::{LightGBMError, LightGBMResult};
+#[cfg(feature = "ffi")]
+use crate::model::Booster;
+use crate::model_format::{threshold_to_f32, TextModel, TreeModel};
+use crate::objective::Objective;
+use std::path::Path;
+
+const IR_VERSION: i64 = 8;
+const OPSET_VERSION: i64 = 13;
+const ML_OPSET_VERSION: i64 = 3;
+
+/// `TensorProto.DataType` values
+const TENSOR_FLOAT: i64 = 1;
+const TENSOR_INT64: i64 = 7;
+
+/// `AttributeProto.AttributeType` values
+const ATTRIBUTE_INT: i64 = 2;
+const ATTRIBUTE_STRING: i64 = 3;
+const ATTRIBUTE_FLOATS: i64 = 6;
+const ATTRIBUTE_INTS: i64 = 7;
+const ATTRIBUTE_STRINGS: i64 = 8;
+
+impl TextModel {
+    /// Convert the model to an ONNX graph using the `ai.onnx.ml` tree ensemble operators
+    ///
+    /// Binary (`binary`), multiclass (`multiclass`) and one-vs-all (`multiclassova`) models
+    /// become a `TreeEnsembleClassifier` with a `LOGISTIC` or `SOFTMAX` post-transform and
+    /// outputs `label` and `probabilities` (of shape `[N, 2]` for binary models, whose second
+    /// column is LightGBM's prediction); other objectives become a `TreeEnsembleRegressor`
+    /// with output `variable`, followed by `Exp`, `Sigmoid`, `Softplus` or `x * |x|` nodes for
+    /// the objectives with an output transform. The input is a float tensor `input` of shape
+    /// `[N, num_features]`.
+    ///
+    /// Thresholds are rounded down to `f32`, so every input is routed as in LightGBM. Values
+    /// LightGBM reads as zero are zeroed (or made missing, for features whose splits treat zero
+    /// as missing) ahead of the trees, and each categorical split becomes a chain of
+    /// `BRANCH_EQ` nodes, one per category, sharing the left subtree. Categorical inputs must
+    /// be whole numbers: LightGBM truncates other values (reading 3.9 as category 3), whereas
+    /// they match no `BRANCH_EQ` node here and go right. Leaf values are stored as `f32`, and
+    /// linear trees are not supported.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lightgbm_rust::model_format::TextModel;
+    ///
+    /// let model = TextModel::load("model.txt").unwrap();
+    /// model.save_onnx("model.onnx").unwrap();
+    /// ```
+    pub fn to_onnx(&self) -> LightGBMResult<Vec<u8>> {
+        let header = &self.header;
+        let objective = Objective::parse(header.objective.as_deref())?;
+        let num_features = usize::try_from(header.max_feature_idx + 1).unwrap_or(0);
+        let num_outputs = header.num_tree_per_iteration.max(1);
+        if self.trees.len() % num_outputs != 0 {
+            return Err(LightGBMError {
+                description: format!(
+                    "Model has {} trees, not a multiple of {} trees per iteration",
+                    self.trees.len(),
+                    num_outputs
+                ),
+            });
+        }
+
+        // Averaging and the sigmoid parameter are folded into the leaf weights
+        let mut scale = 1.0;
+        if header.average_output {
+            scale /= (self.trees.len() / num_outputs).max(1) as f64;
+        }
+        if let Objective::Sigmoid(sigmoid) | Objective::SigmoidPerClass(sigmoid) = objective {
+            scale *= sigmoid;
+        }
+
+        let mut ensemble = Ensemble::default();
+        for (t, tree) in self.trees.iter().enumerate() {
+            check_tree(tree, num_features).map_err(|e| LightGBMError {
+                description: format!("Tree {}: {}", t, e),
+            })?;
+            ensemble.add_tree(t as i64, tree, (t % num_outputs) as i64, scale);
+        }
+
+        let mut graph = Message::default();
+        graph.string(2, "lightgbm");
+        graph.message(1, node("Abs", "", &["input"], &["abs"], Vec::new()));
+        graph.message(
+            1,
+            node(
+                "LessOrEqual",
+                "",
+                &["abs", "zero_threshold"],
+                &["is_zero"],
+                Vec::new(),
+            ),
+        );
+        graph.message(
+            1,
+            node(
+                "Where",
+                "",
+                &["is_zero", "zero_value", "input"],
+                &["features"],
+                Vec::new(),
+            ),
+        );
+        graph.message(5, tensor("zero_threshold", &[], &[ZERO_THRESHOLD]));
+        graph.message(
+            5,
+            tensor(
+                "zero_value",
+                &[num_features as i64],
+                &zero_values(&self.trees, num_features)?,
+            ),
+        );
+        graph.message(
+            11,
+            value_info("input", TENSOR_FLOAT, &[None, Some(num_features)]),
+        );
+
+        match objective {
+            Objective::Sigmoid(_) | Objective::Softmax | Objective::SigmoidPerClass(_) => {
+                // A binary classifier has a single score, for the second class
+                let num_classes = if num_outputs == 1 { 2 } else { num_outputs };
+                let post_transform = match objective {
+                    Objective::Softmax => "SOFTMAX",
+                    _ => "LOGISTIC",
+                };
+                let mut attributes = ensemble.node_attributes();
+                attributes.extend([
+                    attribute_ints(
+                        "classlabels_int64s",
+                        &(0..num_classes as i64).collect::<Vec<_>>(),
+                    ),
+                    attribute_ints("class_treeids", &ensemble.leaf_tree_ids),
+                    attribute_ints("class_nodeids", &ensemble.leaf_node_ids),
+                    attribute_ints("class_ids", &ensemble.leaf_targets),
+                    attribute_floats("class_weights", &ensemble.leaf_weights),
+                    attribute_string("post_transform", post_transform),
+                ]);
+                graph.message(
+                    1,
+                    node(
+                        "TreeEnsembleClassifier",
+                        "ai.onnx.ml",
+                        &["features"],
+                        &["label", "probabilities"],
+                        attributes,
+                    ),
+                );
+                graph.message(12, value_info("label", TENSOR_INT64, &[None]));
+                graph.message(
+                    12,
+                    value_info("probabilities", TENSOR_FLOAT, &[None, Some(num_classes)]),
+                );
+            }
+            _ => {
+                let raw = if objective == Objective::Identity {
+                    "variable"
+                } else {
+                    "raw_score"
+                };
+                let mut attributes = ensemble.node_attributes();
+                attributes.extend([
+                    attribute_int("n_targets", num_outputs as i64),
+                    attribute_ints("target_treeids", &ensemble.leaf_tree_ids),
+                    attribute_ints("target_nodeids", &ensemble.leaf_node_ids),
+                    attribute_ints("target_ids", &ensemble.leaf_targets),
+                    attribute_floats("target_weights", &ensemble.leaf_weights),
+                    attribute_string("post_transform", "NONE"),
+                ]);
+                graph.message(
+                    1,
+                    node(
+                        "TreeEnsembleRegressor",
+                        "ai.onnx.ml",
+                        &["features"],
+                        &[raw],
+                        attributes,
+                    ),
+                );
+                for transform in output_transform(objective) {
+                    graph.message(1, transform);
+                }
+                graph.message(
+                    12,
+                    value_info("variable", TENSOR_FLOAT, &[None, Some(num_outputs)]),
+                );
+            }
+        }
+
+        let mut model = Message::default();
+        model.varint(1, IR_VERSION);
+        model.string(2, "lightgbm-rust");
+        model.string(3, env!("CARGO_PKG_VERSION"));
+        model.message(8, opset("", OPSET_VERSION));
+        model.message(8, opset("ai.onnx.ml", ML_OPSET_VERSION));
+        model.message(7, graph);
+        Ok(model.0)
+    }
+
+    /// Write the model to a `.onnx` file, see [`to_onnx`](Self::to_onnx)
+    pub fn save_onnx<P: AsRef<Path>>(&self, path: P) -> LightGBMResult<()> {
+        std::fs::write(path.as_ref(), self.to_onnx()?).map_err(|e| LightGBMError {
+            description: format!("Failed to write ONNX file: {}", e),
+        })
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl Booster {
+    /// Convert the model to an ONNX graph, see [`TextModel::to_onnx`]
+    pub fn to_onnx(&self) -> LightGBMResult<Vec<u8>> {
+        self.to_text_model()?.to_onnx()
+    }
+
+    /// Write the model to a `.onnx` file, see [`TextModel::to_onnx`]
+    pub fn save_onnx<P: AsRef<Path>>(&self, path: P) -> LightGBMResult<()> {
+        self.to_text_model()?.save_onnx(path)
+    }
+}
+
+/// Node and leaf attributes shared by `TreeEnsembleRegressor` and `TreeEnsembleClassifier`
+#[derive(Default)]
+struct Ensemble {
+    tree_ids: Vec<i64>,
+    node_ids: Vec<i64>,
+    feature_ids: Vec<i64>,
+    modes: Vec<&'static str>,
+    values: Vec<f32>,
+    true_ids: Vec<i64>,
+    false_ids: Vec<i64>,
+    missing_tracks_true: Vec<i64>,
+    leaf_tree_ids: Vec<i64>,
+    leaf_node_ids: Vec<i64>,
+    leaf_targets: Vec<i64>,
+    leaf_weights: Vec<f32>,
+}
+
+/// An ONNX tree node
+struct OnnxNode {
+    id: i64,
+    feature: i64,
+    mode: &'static str,
+    value: f32,
+    true_id: i64,
+    false_id: i64,
+    missing_tracks_true: bool,
+}
+
+/// Converts one LightGBM tree, numbering its ONNX nodes from 0
+struct TreeBuilder<'a> {
+    tree: &'a TreeModel,
+    scale: f64,
+    nodes: Vec<OnnxNode>,
+    /// `(node id, weight)` of each leaf
+    leaves: Vec<(i64, f32)>,
+}
+
+impl Ensemble {
+    fn add_tree(&mut self, tree_id: i64, tree: &TreeModel, target: i64, scale: f64) {
+        let mut builder = TreeBuilder {
+            tree,
+            scale,
+            nodes: Vec::new(),
+            leaves: Vec::new(),
+        };
+        let root = builder.build(if tree.num_leaves > 1 { 0 } else { -1 });
+
+        // ONNX Runtime takes the first node of each tree as its root
+        let mut nodes = builder.nodes;
+        if let Some(position) = nodes.iter().position(|node| node.id == root) {
+            nodes[..=position].rotate_right(1);
+        }
+        for node in nodes {
+            self.tree_ids.push(tree_id);
+            self.node_ids.push(node.id);
+            self.feature_ids.push(node.feature);
+            self.modes.push(node.mode);
+            self.values.push(node.value);
+            self.true_ids.push(node.true_id);
+            self.false_ids.push(node.false_id);
+            self.missing_tracks_true
+                .push(node.missing_tracks_true as i64);
+        }
+        for (id, weight) in builder.leaves {
+            self.leaf_tree_ids.push(tree_id);
+            self.leaf_node_ids.push(id);
+            self.leaf_targets.push(target);
+            self.leaf_weights.push(weight);
+        }
+    }
+
+    fn node_attributes(&self) -> Vec<Message> {
+        vec![
+            attribute_ints("nodes_treeids", &self.tree_ids),
+            attribute_ints("nodes_nodeids", &self.node_ids),
+            attribute_ints("nodes_featureids", &self.feature_ids),
+            attribute_strings("nodes_modes", &self.modes),
+            attribute_floats("nodes_values", &self.values),
+            attribute_ints("nodes_truenodeids", &self.true_ids),
+            attribute_ints("nodes_falsenodeids", &self.false_ids),
+            attribute_ints("nodes_missing_value_tracks_true", &self.missing_tracks_true),
+        ]
+    }
+}
+
+impl TreeBuilder<'_> {
+    /// Add the subtree of a LightGBM node (`!leaf` for leaves) and return its ONNX node id
+    fn build(&mut self, node: i32) -> i64 {
+        let tree = self.tree;
+        if node < 0 {
+            let id = self.push(0, "LEAF", 0.0, false);
+            let weight = tree.leaf_value[!node as usize] * self.scale;
+            self.leaves.push((id, weight as f32));
+            return id;
+        }
+
+        let n = node as usize;
+        let decision_type = tree.decision_type[n] as u8;
+        let feature = tree.split_feature[n] as i64;
+        if decision_type & CATEGORICAL_MASK != 0 {
+            // One equality test per category, all leading to the left subtree; NaN goes right
+            let left = self.build(tree.left_child[n]);
+            let mut next = self.build(tree.right_child[n]);
+            for category in categories(tree, n).into_iter().rev() {
+                let id = self.push(feature, "BRANCH_EQ", category as f32, false);
+                let split = self.nodes.last_mut().expect("node was just pushed");
+                split.true_id = left;
+                split.false_id = next;
+                next = id;
+            }
+            return next;
+        }
+
+        let threshold = tree.threshold[n];
+        let missing_tracks_true = match missing_type(decision_type) {
+            // Zero inputs are made NaN ahead of the trees for these features
+            MISSING_TYPE_ZERO | MISSING_TYPE_NAN => decision_type & DEFAULT_LEFT_MASK != 0,
+            // NaN is read as zero
+            _ => 0.0 <= threshold,
+        };
+        let id = self.push(
+            feature,
+            "BRANCH_LEQ",
+            threshold_to_f32(threshold),
+            missing_tracks_true,
+        );
+        let index = self.nodes.len() - 1;
+        let left = self.build(tree.left_child[n]);
+        let right = self.build(tree.right_child[n]);
+        self.nodes[index].true_id = left;
+        self.nodes[index].false_id = right;
+        id
+    }
+
+    fn push(&mut self, feature: i64, mode: &'static str, value: f32, tracks_true: bool) -> i64 {
+        let id = self.nodes.len() as i64;
+        self.nodes.push(OnnxNode {
+            id,
+            feature,
+            mode,
+            value,
+            true_id: 0,
+            false_id: 0,
+            missing_tracks_true: tracks_true,
+        });
+        id
+    }
+}
+
+/// Check the tree's arrays, and that children follow their parent so conversion terminates
+fn check_tree(tree: &TreeModel, num_features: usize) -> LightGBMResult<()> {
+    if tree.is_linear {
+        return Err(LightGBMError {
+            description: "linear trees cannot be exported to ONNX".to_string(),
+        });
+    }
+    let num_internal = tree.num_internal();
+    if tree.num_leaves == 0
+        || tree.leaf_value.len() != tree.num_leaves
+        || [
+            tree.split_feature.len(),
+            tree.threshold.len(),
+            tree.decision_type.len(),
+            tree.left_child.len(),
+            tree.right_child.len(),
+        ]
+        .iter()
+        .any(|&len| len != num_internal)
+    {
+        return Err(LightGBMError {
+            description: format!("node arrays do not match {} leaves", tree.num_leaves),
+        });
+    }
+
+    for node in 0..num_internal {
+        let valid_child = |child: i32| {
+            if child >= 0 {
+                (child as usize) > node && (child as usize) < num_internal
+            } else {
+                (!child as usize) < tree.num_leaves
+            }
+        };
+        let feature = tree.split_feature[node];
+        let valid = valid_child(tree.left_child[node])
+            && valid_child(tree.right_child[node])
+            && usize::try_from(feature).is_ok_and(|f| f < num_features)
+            && (tree.decision_type[node] as u8 & CATEGORICAL_MASK == 0
+                || bitset_range(tree, node).is_some());
+        if !valid {
+            return Err(LightGBMError {
+                description: format!("invalid split at node {}", node),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Range of `cat_threshold` holding the bitset of a categorical node
+fn bitset_range(tree: &TreeModel, node: usize) -> Option<std::ops::Range<usize>> {
+    let cat_idx = tree.threshold[node];
+    if !(cat_idx >= 0.0 && (cat_idx as usize) < tree.num_cat) {
+        return None;
+    }
+    let start = usize::try_from(*tree.cat_boundaries.get(cat_idx as usize)?).ok()?;
+    let end = usize::try_from(*tree.cat_boundaries.get(cat_idx as usize + 1)?).ok()?;
+    (start <= end && end <= tree.cat_threshold.len()).then_some(start..end)
+}
+
+/// Categories sent left by a categorical node
+fn categories(tree: &TreeModel, node: usize) -> Vec<usize> {
+    let range = bitset_range(tree, node).unwrap_or_default();
+    tree.cat_threshold[range]
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &word)| {
+            (0..32)
+                .filter(move |bit| (word >> bit) & 1 != 0)
+                .map(move |bit| i * 32 + bit)
+        })
+        .collect()
+}
+
+/// Replacement of inputs LightGBM reads as zero: NaN for features whose splits treat zero
+/// as missing, else zero
+fn zero_values(trees: &[TreeModel], num_features: usize) -> LightGBMResult<Vec<f32>> {
+    let mut zero_missing = vec![false; num_features];
+    let mut zero_present = vec![false; num_features];
+    for tree in trees {
+        for (node, &decision_type) in tree.decision_type.iter().enumerate() {
+            let decision_type = decision_type as u8;
+            let feature = tree.split_feature[node] as usize;
+            if decision_type & CATEGORICAL_MASK == 0
+                && missing_type(decision_type) == MISSING_TYPE_ZERO
+            {
+                zero_missing[feature] = true;
+            } else if decision_type & CATEGORICAL_MASK != 0
+                || missing_type(decision_type) == MISSING_TYPE_NAN
+            {
+                zero_present[feature] = true;
+            }
+        }
+    }
+
+    zero_missing
+        .iter()
+        .zip(&zero_present)
+        .enumerate()
+        .map(|(feature, (&missing, &present))| match (missing, present) {
+            (true, true) => Err(LightGBMError {
+                description: format!(
+                    "Feature {} treats zero as missing in some splits but not in others",
+                    feature
+                ),
+            }),
+            (true, false) => Ok(f32::NAN),
+            _ => Ok(0.0),
+        })
+        .collect()
+}
+
+/// Nodes applying a regression objective's transform to `raw_score`, producing `variable`
+fn output_transform(objective: Objective) -> Vec<Message> {
+    let unary = |op_type| vec![node(op_type, "", &["raw_score"], &["variable"], Vec::new())];
+    match objective {
+        Objective::Square => vec![
+            node("Abs", "", &["raw_score"], &["raw_abs"], Vec::new()),
+            node(
+                "Mul",
+                "",
+                &["raw_score", "raw_abs"],
+                &["variable"],
+                Vec::new(),
+            ),
+        ],
+        Objective::Exp => unary("Exp"),
+        Objective::CrossEntropy => unary("Sigmoid"),
+        Objective::CrossEntropyLambda => unary("Softplus"),
+        _ => Vec::new(),
+    }
+}
+
+fn node(
+    op_type: &str,
+    domain: &str,
+    inputs: &[&str],
+    outputs: &[&str],
+    attributes: Vec<Message>,
+) -> Message {
+    let mut node = Message::default();
+    for input in inputs {
+        node.string(1, input);
+    }
+    for output in outputs {
+        node.string(2, output);
+    }
+    node.string(4, op_type);
+    if !domain.is_empty() {
+        node.string(7, domain);
+    }
+    for attribute in attributes {
+        node.message(5, attribute);
+    }
+    node
+}
+
+fn attribute(name: &str, attribute_type: i64) -> Message {
+    let mut attribute = Message::default();
+    attribute.string(1, name);
+    attribute.varint(20, attribute_type);
+    attribute
+}
+
+fn attribute_int(name: &str, value: i64) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_INT);
+    attribute.varint(3, value);
+    attribute
+}
+
+fn attribute_string(name: &str, value: &str) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_STRING);
+    attribute.string(4, value);
+    attribute
+}
+
+fn attribute_floats(name: &str, values: &[f32]) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_FLOATS);
+    attribute.packed_floats(7, values);
+    attribute
+}
+
+fn attribute_ints(name: &str, values: &[i64]) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_INTS);
+    attribute.packed_varints(8, values);
+    attribute
+}
+
+fn attribute_strings(name: &str, values: &[&str]) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_STRINGS);
+    for value in values {
+        attribute.string(9, value);
+    }
+    attribute
+}
+
+fn tensor(name: &str, dims: &[i64], values: &[f32]) -> Message {
+    let mut tensor = Message::default();
+    tensor.packed_varints(1, dims);
+    tensor.varint(2, TENSOR_FLOAT);
+    tensor.packed_floats(4, values);
+    tensor.string(8, name);
+    tensor
+}
+
+/// A tensor input or output; `None` dimensions are the batch size `N`
+fn value_info(name: &str, elem_type: i64, dims: &[Option<usize>]) -> Message {
+    let mut shape = Message::default();
+    for dim in dims {
+        let mut dimension = Message::default();
+        match dim {
+            Some(size) => dimension.varint(1, *size as i64),
+            None => dimension.string(2, "N"),
+        }
+        shape.message(1, dimension);
+    }
+    let mut tensor_type = Message::default();
+    tensor_type.varint(1, elem_type);
+    tensor_type.message(2, shape);
+    let mut value_type = Message::default();
+    value_type.message(1, tensor_type);
+
+    let mut info = Message::default();
+    info.string(1, name);
+    info.message(2, value_type);
+    info
+}
+
+fn opset(domain: &str, version: i64) -> Message {
+    let mut opset = Message::default();
+    opset.string(1, domain);
+    opset.varint(2, version);
+    opset
+}
+
+/// Protocol buffers encoding of a message, enough for the ONNX messages written here
+#[derive(Default)]
+struct Message(Vec<u8>);
+
+impl Message {
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.raw_varint(u64::from(field) << 3 | u64::from(wire_type));
+    }
+
+    fn varint(&mut self, field: u32, value: i64) {
+        self.key(field, 0);
+        self.raw_varint(value as u64);
+    }
+
+    fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.key(field, 2);
+        self.raw_varint(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn string(&mut self, field: u32, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    fn message(&mut self, field: u32, message: Message) {
+        self.bytes(field, &message.0);
+    }
+
+    fn packed_varints(&mut self, field: u32, values: &[i64]) {
+        let mut packed = Message::default();
+        for &value in values {
+            packed.raw_varint(value as u64);
+        }
+        self.bytes(field, &packed.0);
+    }
+
+    fn packed_floats(&mut self, field: u32, values: &[f32]) {
+        let packed: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        self.bytes(field, &packed);
+    }
+}
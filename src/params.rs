@@ -0,0 +1,96 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::Booster;
+use crate::sys;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Training parameters stored in a model's `parameters:` section
+///
+/// Common parameters are available as typed fields (`None` or empty when the model does not
+/// record them); every parameter, including those without a field, is kept in the raw map.
+///
+/// # Example
+/// ```ignore
+/// let params = booster.params()?;
+/// if params.objective.as_deref() != Some("binary") {
+///     return Err("expected a binary classifier".into());
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ModelParams {
+    /// Objective, e.g. `binary`, `multiclass` or `regression`
+    pub objective: Option<String>,
+    /// Boosting type, e.g. `gbdt`, `dart` or `rf`
+    pub boosting: Option<String>,
+    /// Number of classes for multiclass objectives
+    pub num_class: Option<i32>,
+    /// Evaluation metrics
+    pub metric: Vec<String>,
+    /// Learning rate (shrinkage)
+    pub learning_rate: Option<f64>,
+    /// Number of boosting iterations requested
+    pub num_iterations: Option<i32>,
+    /// Maximum number of leaves per tree
+    pub num_leaves: Option<i32>,
+    /// Maximum tree depth, `<= 0` meaning unlimited
+    pub max_depth: Option<i32>,
+    /// Minimum number of samples in a leaf
+    pub min_data_in_leaf: Option<i32>,
+    /// Fraction of features sampled per tree
+    pub feature_fraction: Option<f64>,
+    /// Fraction of samples used per bagging round
+    pub bagging_fraction: Option<f64>,
+    /// L1 regularization
+    pub lambda_l1: Option<f64>,
+    /// L2 regularization
+    pub lambda_l2: Option<f64>,
+    /// Sigmoid scale of binary and ranking objectives
+    pub sigmoid: Option<f64>,
+    /// Whether leaves are linear models
+    pub linear_tree: Option<bool>,
+    /// Whether zero is treated as missing
+    pub zero_as_missing: Option<bool>,
+    /// Categorical features as given to training
+    pub categorical_feature: Option<String>,
+    #[serde(skip)]
+    raw: Map<String, Value>,
+}
+
+impl ModelParams {
+    /// Parse the JSON returned by `LGBM_BoosterGetLoadedParam`
+    pub fn from_json(json: &str) -> LightGBMResult<Self> {
+        let raw: Map<String, Value> = serde_json::from_str(json).map_err(|e| LightGBMError {
+            description: format!("Failed to parse model parameters: {}", e),
+        })?;
+        let mut params: ModelParams =
+            serde_json::from_value(Value::Object(raw.clone())).map_err(|e| LightGBMError {
+                description: format!("Unexpected model parameter type: {}", e),
+            })?;
+        params.raw = raw;
+        Ok(params)
+    }
+
+    /// Get any parameter by its LightGBM name
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.raw.get(name)
+    }
+
+    /// All parameters by name
+    pub fn raw(&self) -> &Map<String, Value> {
+        &self.raw
+    }
+}
+
+impl Booster {
+    /// Get the training parameters the model was saved with
+    ///
+    /// Models without a `parameters:` section (e.g. saved by very old LightGBM versions)
+    /// yield empty parameters.
+    pub fn params(&self) -> LightGBMResult<ModelParams> {
+        let json = self.read_string(|buffer_len, out_len, out_str| unsafe {
+            sys::LGBM_BoosterGetLoadedParam(self.handle, buffer_len, out_len, out_str)
+        })?;
+        ModelParams::from_json(&json)
+    }
+}
@@ -0,0 +1,261 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::{Booster, FloatInput};
+
+/// How [`InputPolicy`] handles infinite input values
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InfinityPolicy {
+    /// Pass infinities to LightGBM unchanged
+    Keep,
+    /// Fail the prediction, reporting the first infinite value
+    Reject,
+    /// Replace `±inf` with the largest finite value of the same sign
+    Clamp,
+    /// Replace infinities with NaN, i.e. treat them as missing
+    Missing,
+}
+
+/// What [`InputPolicy`] did to a single input value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Adjustment {
+    /// A sentinel value was replaced with NaN
+    SentinelToMissing,
+    /// An infinity was replaced with NaN
+    InfinityToMissing,
+    /// An infinity was clamped to the largest finite value
+    InfinityClamped,
+}
+
+/// An input value changed by [`InputPolicy`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdjustedValue {
+    /// Row of the value
+    pub row: usize,
+    /// Column (feature) of the value
+    pub col: usize,
+    /// Value before adjustment
+    pub original: f64,
+    /// What was done to it
+    pub adjustment: Adjustment,
+}
+
+/// Values changed while applying an [`InputPolicy`], in row-major order
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputReport {
+    /// Every adjusted value
+    pub adjusted: Vec<AdjustedValue>,
+}
+
+impl InputReport {
+    /// Whether any value was adjusted
+    pub fn is_empty(&self) -> bool {
+        self.adjusted.is_empty()
+    }
+
+    /// Rows with at least one adjusted value, ascending
+    pub fn rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = self.adjusted.iter().map(|a| a.row).collect();
+        rows.dedup();
+        rows
+    }
+
+    /// Columns with at least one adjusted value, ascending
+    pub fn columns(&self) -> Vec<usize> {
+        let mut columns: Vec<usize> = self.adjusted.iter().map(|a| a.col).collect();
+        columns.sort_unstable();
+        columns.dedup();
+        columns
+    }
+}
+
+/// Cleaning rules applied to input data before prediction
+///
+/// LightGBM marks missing values with NaN. Upstream systems that encode missing data with
+/// sentinels such as `-999` can map them to NaN per feature here, so that the model sends
+/// them down the learned default branch instead of comparing them against thresholds.
+///
+/// How NaN is then handled follows the model's per-feature missing type: features trained
+/// with missing values use the default branch, features trained with `zero_as_missing=true`
+/// treat NaN like zero (both are missing), and features that saw no missing values during
+/// training convert NaN to zero.
+///
+/// # Example
+/// ```ignore
+/// use lightgbm_rust::{InfinityPolicy, InputPolicy};
+///
+/// let policy = InputPolicy::new()
+///     .with_sentinel(0, -999.0)
+///     .with_sentinel(3, 0.0)
+///     .with_infinity(InfinityPolicy::Reject);
+/// let (predictions, report) = booster.predict_with_policy(&data, 2, 4, predict_type::NORMAL, &policy)?;
+/// println!("Rows with sentinels or infinities: {:?}", report.rows());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputPolicy {
+    sentinels: Vec<(usize, f64)>,
+    infinity: InfinityPolicy,
+    reject_nan: bool,
+}
+
+impl Default for InputPolicy {
+    fn default() -> Self {
+        InputPolicy {
+            sentinels: Vec::new(),
+            infinity: InfinityPolicy::Keep,
+            reject_nan: false,
+        }
+    }
+}
+
+impl InputPolicy {
+    /// Create a policy that leaves all values unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat `value` in column `feature` as missing (NaN)
+    pub fn with_sentinel(mut self, feature: usize, value: f64) -> Self {
+        self.sentinels.push((feature, value));
+        self
+    }
+
+    /// Set how infinite values are handled (default [`InfinityPolicy::Keep`])
+    pub fn with_infinity(mut self, infinity: InfinityPolicy) -> Self {
+        self.infinity = infinity;
+        self
+    }
+
+    /// Fail the prediction if the input already contains NaN (default `false`)
+    ///
+    /// Sentinels mapped to NaN by this policy are not rejected.
+    pub fn with_reject_nan(mut self, reject_nan: bool) -> Self {
+        self.reject_nan = reject_nan;
+        self
+    }
+
+    /// Apply the policy to a row-major matrix, returning the cleaned data and what changed
+    ///
+    /// Fails if `num_cols` is zero or `data` is not a whole number of rows.
+    pub fn apply<T: Copy + Into<f64>>(
+        &self,
+        data: &[T],
+        num_cols: usize,
+    ) -> LightGBMResult<(Vec<f64>, InputReport)> {
+        if num_cols == 0 || data.len() % num_cols != 0 {
+            return Err(LightGBMError {
+                description: format!(
+                    "Input data of {} elements is not a whole number of rows of {} columns",
+                    data.len(),
+                    num_cols
+                ),
+            });
+        }
+
+        // Only columns that occur in `data` need an entry
+        let mut column_sentinels: Vec<Vec<f64>> = vec![Vec::new(); num_cols.min(data.len())];
+        for &(feature, value) in &self.sentinels {
+            if feature >= num_cols {
+                return Err(LightGBMError {
+                    description: format!(
+                        "Sentinel feature index {} out of range for {} columns",
+                        feature, num_cols
+                    ),
+                });
+            }
+            if let Some(sentinels) = column_sentinels.get_mut(feature) {
+                sentinels.push(value);
+            }
+        }
+
+        let mut report = InputReport::default();
+        let mut cleaned = Vec::with_capacity(data.len());
+
+        for (i, value) in data.iter().enumerate() {
+            let value: f64 = (*value).into();
+            let (row, col) = (i / num_cols, i % num_cols);
+            let mut adjust = |adjustment, cleaned_value| {
+                report.adjusted.push(AdjustedValue {
+                    row,
+                    col,
+                    original: value,
+                    adjustment,
+                });
+                cleaned_value
+            };
+
+            let cleaned_value = if column_sentinels[col].contains(&value) {
+                adjust(Adjustment::SentinelToMissing, f64::NAN)
+            } else if value.is_infinite() {
+                match self.infinity {
+                    InfinityPolicy::Keep => value,
+                    InfinityPolicy::Reject => {
+                        return Err(LightGBMError {
+                            description: format!(
+                                "Infinite input value at row {}, column {}",
+                                row, col
+                            ),
+                        })
+                    }
+                    InfinityPolicy::Clamp => {
+                        adjust(Adjustment::InfinityClamped, f64::MAX.copysign(value))
+                    }
+                    InfinityPolicy::Missing => adjust(Adjustment::InfinityToMissing, f64::NAN),
+                }
+            } else if value.is_nan() && self.reject_nan {
+                return Err(LightGBMError {
+                    description: format!("NaN input value at row {}, column {}", row, col),
+                });
+            } else {
+                value
+            };
+            cleaned.push(cleaned_value);
+        }
+
+        Ok((cleaned, report))
+    }
+}
+
+impl Booster {
+    /// Predict for a dense matrix after applying an [`InputPolicy`]
+    ///
+    /// # Arguments
+    /// * `data` - Input data in row-major format (flattened 2D array), can be &[f32] or &[f64]
+    /// * `num_rows` - Number of rows (samples)
+    /// * `num_cols` - Number of columns (features)
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    /// * `policy` - Sentinel and NaN/Inf handling rules
+    ///
+    /// # Returns
+    /// Vector of predictions and a report of the values the policy changed
+    pub fn predict_with_policy<T: FloatInput + Copy + Into<f64>>(
+        &self,
+        data: &[T],
+        num_rows: i32,
+        num_cols: i32,
+        predict_type: i32,
+        policy: &InputPolicy,
+    ) -> LightGBMResult<(Vec<f64>, InputReport)> {
+        let expected_len = (num_rows.max(0) as usize)
+            .checked_mul(num_cols.max(0) as usize)
+            .ok_or_else(|| LightGBMError {
+                description: format!(
+                    "Integer overflow when computing expected data size: num_rows ({}) * num_cols ({})",
+                    num_rows, num_cols
+                ),
+            })?;
+        if expected_len != data.len() {
+            return Err(LightGBMError {
+                description: format!(
+                    "Input data size mismatch: expected {} elements ({}×{}), got {}",
+                    expected_len,
+                    num_rows,
+                    num_cols,
+                    data.len()
+                ),
+            });
+        }
+
+        let (cleaned, report) = policy.apply(data, num_cols.max(0) as usize)?;
+        let predictions = self.predict(&cleaned, num_rows, num_cols, predict_type)?;
+        Ok((predictions, report))
+    }
+}
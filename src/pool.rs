@@ -0,0 +1,183 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::{Booster, FloatInput};
+use rayon::prelude::*;
+use std::ffi::CString;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+/// Default number of rows predicted per chunk
+const DEFAULT_CHUNK_SIZE: usize = 1024;
+
+/// A pool of Boosters loaded from the same model for parallel batch prediction
+///
+/// Large inputs are split into chunks of rows which are predicted concurrently on the rayon
+/// thread pool, each chunk on its own Booster handle, and the results are reassembled in
+/// input order.
+///
+/// # Example
+/// ```ignore
+/// use lightgbm_rust::{BoosterPool, predict_type};
+///
+/// let pool = BoosterPool::load("model.txt", 8)?
+///     .with_chunk_size(4096)
+///     .with_num_threads(1);
+/// let predictions = pool.predict(&data, num_rows, num_cols, predict_type::NORMAL)?;
+/// ```
+pub struct BoosterPool {
+    boosters: Vec<Mutex<PooledBooster>>,
+    chunk_size: usize,
+    parameter: CString,
+}
+
+/// A Booster that may be moved between threads
+///
+/// Each handle is only ever used by one thread at a time (it is guarded by a `Mutex`), which
+/// is the same guarantee as creating one Booster per thread.
+struct PooledBooster(Booster);
+
+unsafe impl Send for PooledBooster {}
+
+impl BoosterPool {
+    /// Load `num_boosters` handles of a model from a file
+    pub fn load<P: AsRef<Path>>(path: P, num_boosters: usize) -> LightGBMResult<Self> {
+        let model_str = fs::read_to_string(path.as_ref()).map_err(|e| LightGBMError {
+            description: format!("Failed to read model file: {}", e),
+        })?;
+        Self::load_from_string(&model_str, num_boosters)
+    }
+
+    /// Load `num_boosters` handles of a model from a string buffer
+    ///
+    /// # Arguments
+    /// * `model_str` - Model content as a string (text format)
+    /// * `num_boosters` - Number of handles, i.e. how many chunks can be predicted at once
+    pub fn load_from_string(model_str: &str, num_boosters: usize) -> LightGBMResult<Self> {
+        if num_boosters == 0 {
+            return Err(LightGBMError {
+                description: "BoosterPool needs at least one Booster".to_string(),
+            });
+        }
+
+        let boosters = (0..num_boosters)
+            .map(|_| Booster::load_from_string(model_str).map(|b| Mutex::new(PooledBooster(b))))
+            .collect::<LightGBMResult<Vec<_>>>()?;
+
+        Ok(BoosterPool {
+            boosters,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            parameter: num_threads_parameter(1),
+        })
+    }
+
+    /// Set the number of rows predicted per chunk (default 1024)
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Set the number of LightGBM threads used by each handle (default 1)
+    ///
+    /// Parallelism already comes from predicting chunks concurrently, so more than one
+    /// thread per handle usually oversubscribes the CPU.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.parameter = num_threads_parameter(num_threads.max(1));
+        self
+    }
+
+    /// Number of Booster handles in the pool
+    pub fn num_boosters(&self) -> usize {
+        self.boosters.len()
+    }
+
+    /// Predict for a dense matrix in parallel
+    ///
+    /// # Arguments
+    /// * `data` - Input data in row-major format (flattened 2D array), can be &[f32] or &[f64]
+    /// * `num_rows` - Number of rows (samples)
+    /// * `num_cols` - Number of columns (features)
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    ///
+    /// # Returns
+    /// Vector of predictions, identical to [`Booster::predict`] on the whole input
+    pub fn predict<T: FloatInput + Sync>(
+        &self,
+        data: &[T],
+        num_rows: i32,
+        num_cols: i32,
+        predict_type: i32,
+    ) -> LightGBMResult<Vec<f64>> {
+        let expected_len = (num_rows as usize)
+            .checked_mul(num_cols as usize)
+            .ok_or_else(|| LightGBMError {
+                description: format!(
+                    "Integer overflow when computing expected data size: num_rows ({}) * num_cols ({})",
+                    num_rows, num_cols
+                ),
+            })?;
+
+        if expected_len != data.len() {
+            return Err(LightGBMError {
+                description: format!(
+                    "Input data size mismatch: expected {} elements ({}×{}), got {}",
+                    expected_len,
+                    num_rows,
+                    num_cols,
+                    data.len()
+                ),
+            });
+        }
+
+        if expected_len == 0 {
+            return self.lock(0)?.0.predict_mat_with_parameter(
+                data,
+                num_rows,
+                num_cols,
+                true,
+                predict_type,
+                Some(&self.parameter),
+            );
+        }
+
+        let chunks: Vec<Vec<f64>> = data
+            .par_chunks(self.chunk_size * num_cols as usize)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let booster = self.lock(i)?;
+                booster.0.predict_mat_with_parameter(
+                    chunk,
+                    (chunk.len() / num_cols as usize) as i32,
+                    num_cols,
+                    true,
+                    predict_type,
+                    Some(&self.parameter),
+                )
+            })
+            .collect::<LightGBMResult<_>>()?;
+
+        Ok(chunks.concat())
+    }
+
+    /// Lock a free Booster, preferring the one at `hint` and blocking only if all are busy
+    fn lock(&self, hint: usize) -> LightGBMResult<MutexGuard<'_, PooledBooster>> {
+        let n = self.boosters.len();
+        for offset in 0..n {
+            match self.boosters[(hint + offset) % n].try_lock() {
+                Ok(guard) => return Ok(guard),
+                Err(TryLockError::WouldBlock) => continue,
+                Err(TryLockError::Poisoned(_)) => return Err(poisoned()),
+            }
+        }
+        self.boosters[hint % n].lock().map_err(|_| poisoned())
+    }
+}
+
+fn num_threads_parameter(num_threads: usize) -> CString {
+    CString::new(format!("num_threads={}", num_threads)).expect("parameter contains no NUL byte")
+}
+
+fn poisoned() -> LightGBMError {
+    LightGBMError {
+        description: "A Booster in the pool was poisoned by a panicking thread".to_string(),
+    }
+}
@@ -0,0 +1,171 @@
+use crate::error::{LightGBMError, LightGBMResult};
+use crate::model::{Booster, FloatInput};
+use std::marker::PhantomData;
+
+impl Booster {
+    /// Predict lazily over an iterator of rows with bounded memory
+    ///
+    /// Rows are buffered `chunk_size` at a time, each chunk is predicted in one call and its
+    /// results are yielded row by row before the next chunk is pulled from `rows`. At most one
+    /// chunk of input and output is held in memory regardless of how many rows are scored.
+    ///
+    /// # Arguments
+    /// * `rows` - Rows to predict, each a slice-like of `num_cols` f32 or f64 values
+    /// * `num_cols` - Number of columns (features)
+    /// * `predict_type` - Prediction type (see [`predict_type`](crate::predict_type))
+    /// * `chunk_size` - Number of rows predicted per call
+    ///
+    /// # Returns
+    /// Iterator yielding the predictions of each row in input order. A row of the wrong
+    /// length ends the iterator with an error, after the predictions of all rows before it
+    /// (including those buffered in the same chunk). Prediction errors also end it.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let rows = cursor.map(|record| record.features()); // impl Iterator<Item = Vec<f64>>
+    /// for prediction in booster.predict_iter(rows, 4, predict_type::NORMAL, 1024) {
+    ///     let prediction = prediction?;
+    ///     // ...
+    /// }
+    /// ```
+    pub fn predict_iter<I, R, T>(
+        &self,
+        rows: I,
+        num_cols: i32,
+        predict_type: i32,
+        chunk_size: usize,
+    ) -> PredictIter<'_, I::IntoIter, T>
+    where
+        I: IntoIterator<Item = R>,
+        R: AsRef<[T]>,
+        T: FloatInput + Copy,
+    {
+        let chunk_size = chunk_size.max(1);
+        PredictIter {
+            booster: self,
+            rows: rows.into_iter(),
+            num_cols,
+            predict_type,
+            chunk_size,
+            input: Vec::with_capacity(chunk_size * num_cols.max(0) as usize),
+            output: Vec::new(),
+            per_row: 0,
+            position: 0,
+            error: None,
+            done: false,
+            _input_type: PhantomData,
+        }
+    }
+}
+
+/// Iterator returned by [`Booster::predict_iter`]
+pub struct PredictIter<'a, I, T> {
+    booster: &'a Booster,
+    rows: I,
+    num_cols: i32,
+    predict_type: i32,
+    chunk_size: usize,
+    input: Vec<T>,
+    output: Vec<f64>,
+    per_row: usize,
+    position: usize,
+    /// Invalid row met while filling a chunk, reported once the rows before it are yielded
+    error: Option<LightGBMError>,
+    done: bool,
+    _input_type: PhantomData<T>,
+}
+
+impl<I, R, T> PredictIter<'_, I, T>
+where
+    I: Iterator<Item = R>,
+    R: AsRef<[T]>,
+    T: FloatInput + Copy,
+{
+    /// Buffer and predict the next chunk, returning `false` once the input is exhausted
+    ///
+    /// Rows before an invalid row are predicted; the error is returned by the next call.
+    fn fill(&mut self) -> LightGBMResult<bool> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.input.clear();
+        let mut num_rows = 0usize;
+
+        for row in self.rows.by_ref() {
+            let row = row.as_ref();
+            if row.len() != self.num_cols as usize {
+                self.error = Some(LightGBMError {
+                    description: format!(
+                        "Input row size mismatch: expected {} features, got {}",
+                        self.num_cols,
+                        row.len()
+                    ),
+                });
+                break;
+            }
+            self.input.extend_from_slice(row);
+            num_rows += 1;
+            if num_rows == self.chunk_size {
+                break;
+            }
+        }
+
+        if num_rows == 0 {
+            return match self.error.take() {
+                Some(e) => Err(e),
+                None => Ok(false),
+            };
+        }
+
+        self.output = self.booster.predict(
+            &self.input,
+            num_rows as i32,
+            self.num_cols,
+            self.predict_type,
+        )?;
+        self.per_row = self.output.len() / num_rows;
+        self.position = 0;
+        Ok(true)
+    }
+}
+
+impl<I, R, T> Iterator for PredictIter<'_, I, T>
+where
+    I: Iterator<Item = R>,
+    R: AsRef<[T]>,
+    T: FloatInput + Copy,
+{
+    type Item = LightGBMResult<Vec<f64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.position >= self.output.len() {
+            match self.fill() {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        let start = self.position;
+        self.position += self.per_row;
+        Some(Ok(self.output[start..self.position].to_vec()))
+    }
+}
+
+impl<I, R, T> std::iter::FusedIterator for PredictIter<'_, I, T>
+where
+    I: Iterator<Item = R>,
+    R: AsRef<[T]>,
+    T: FloatInput + Copy,
+{
+}
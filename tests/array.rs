@@ -0,0 +1,112 @@
+#![cfg(feature = "ndarray")]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{predict_type, Booster};
+use ndarray::{s, Array2, ShapeBuilder};
+
+fn booster(objective: &str, num_class: usize) -> Booster {
+    // SHAP values are not defined for linear trees
+    Booster::load_from_string(&model_text(&TREES[..3], objective, num_class, 3, false)).unwrap()
+}
+
+fn matrix() -> Array2<f64> {
+    let data = rows();
+    Array2::from_shape_vec((data.len() / 3, 3), data).unwrap()
+}
+
+fn expected(booster: &Booster, data: &Array2<f64>, predict_type: i32) -> Vec<f64> {
+    let flat: Vec<f64> = data.iter().copied().collect();
+    booster
+        .predict(&flat, data.nrows() as i32, 3, predict_type)
+        .unwrap()
+}
+
+#[test]
+fn test_predict_ndarray_layouts() {
+    let booster = booster("multiclass num_class:3", 3);
+    let data = matrix();
+    let num_rows = data.nrows();
+    let expected = expected(&booster, &data, predict_type::NORMAL);
+
+    let fortran =
+        Array2::from_shape_vec((num_rows, 3).f(), data.t().iter().copied().collect()).unwrap();
+    assert!(fortran.t().as_slice().is_some());
+    assert!(fortran
+        .iter()
+        .zip(data.iter())
+        .all(|(a, b)| a.to_bits() == b.to_bits()));
+
+    let mut wide = Array2::zeros((num_rows, 6));
+    wide.slice_mut(s![.., ..;2]).assign(&data);
+    let strided = wide.slice(s![.., ..;2]);
+    assert!(strided.as_slice().is_none() && strided.t().as_slice().is_none());
+
+    for view in [data.view(), fortran.view(), strided] {
+        let actual = booster.predict_ndarray(view, predict_type::NORMAL).unwrap();
+        assert_eq!(actual.dim(), (num_rows, 3));
+        assert_eq!(actual.as_slice().unwrap(), expected.as_slice());
+    }
+}
+
+#[test]
+fn test_predict_ndarray_f32() {
+    let booster = booster("binary", 1);
+    let data = matrix().mapv(|x| x as f32);
+    let flat: Vec<f32> = data.iter().copied().collect();
+    let expected = booster
+        .predict(&flat, data.nrows() as i32, 3, predict_type::RAW_SCORE)
+        .unwrap();
+
+    let actual = booster
+        .predict_ndarray(data.view(), predict_type::RAW_SCORE)
+        .unwrap();
+    assert_eq!(actual.dim(), (data.nrows(), 1));
+    assert_eq!(actual.into_raw_vec_and_offset().0, expected);
+}
+
+#[test]
+fn test_predict_ndarray_leaf_index_shape() {
+    let booster = booster("regression", 1);
+    let data = matrix();
+
+    let actual = booster
+        .predict_ndarray(data.view(), predict_type::LEAF_INDEX)
+        .unwrap();
+    assert_eq!(actual.dim(), (data.nrows(), 3));
+    assert_eq!(
+        actual.as_slice().unwrap(),
+        expected(&booster, &data, predict_type::LEAF_INDEX).as_slice()
+    );
+}
+
+#[test]
+fn test_predict_contrib_ndarray() {
+    for (objective, num_class) in [("regression", 1), ("multiclass num_class:3", 3)] {
+        let booster = booster(objective, num_class);
+        let data = matrix();
+        let expected = expected(&booster, &data, predict_type::CONTRIB);
+
+        let contrib = booster.predict_contrib_ndarray(data.view()).unwrap();
+        assert_eq!(contrib.dim(), (data.nrows(), num_class, 4), "{}", objective);
+        assert_eq!(contrib.as_slice().unwrap(), expected.as_slice());
+
+        // The two-dimensional form lays the classes side by side
+        let flat = booster
+            .predict_ndarray(data.view(), predict_type::CONTRIB)
+            .unwrap();
+        assert_eq!(flat.dim(), (data.nrows(), num_class * 4));
+        assert_eq!(flat.as_slice().unwrap(), expected.as_slice());
+    }
+}
+
+#[test]
+fn test_predict_ndarray_wrong_number_of_columns() {
+    let booster = booster("regression", 1);
+    let data = Array2::<f64>::zeros((2, 4));
+
+    assert!(booster
+        .predict_ndarray(data.view(), predict_type::NORMAL)
+        .is_err());
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{predict_type, AsyncPredictor, BatchConfig, Booster};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn booster() -> Booster {
+    Booster::load_from_string(&model_text(&TREES, "multiclass num_class:3", 3, 3, false)).unwrap()
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+/// Predict every row from its own task and return the results in row order
+async fn predict_concurrently(predictor: Arc<AsyncPredictor>, data: &[f64]) -> Vec<Vec<f64>> {
+    let tasks: Vec<_> = data
+        .chunks(3)
+        .map(|row| {
+            let predictor = Arc::clone(&predictor);
+            let row = row.to_vec();
+            tokio::spawn(async move { predictor.predict(&row).await.unwrap() })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(task.await.unwrap());
+    }
+    results
+}
+
+/// Compare bit patterns, as some outputs are NaN
+fn assert_same(actual: &[f64], expected: &[f64]) {
+    let bits = |values: &[f64]| values.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    assert_eq!(bits(actual), bits(expected));
+}
+
+#[test]
+fn test_batched_rows_match_predict() {
+    let booster = booster();
+    let data = rows();
+    let expected = booster
+        .predict(&data, (data.len() / 3) as i32, 3, predict_type::RAW_SCORE)
+        .unwrap();
+
+    let config = BatchConfig::default()
+        .with_max_batch_size(16)
+        .with_predict_type(predict_type::RAW_SCORE);
+    let predictor = Arc::new(AsyncPredictor::new(booster, config).unwrap());
+    let results = block_on(predict_concurrently(predictor, &data));
+
+    assert!(results.iter().all(|row| row.len() == 3));
+    assert_same(&results.concat(), &expected);
+}
+
+#[test]
+fn test_full_batch_does_not_wait_for_deadline() {
+    let data = &rows()[..8 * 3];
+    let delay = Duration::from_secs(60);
+    let config = BatchConfig::default()
+        .with_max_batch_size(8)
+        .with_max_delay(delay);
+    let predictor = Arc::new(AsyncPredictor::new(booster(), config).unwrap());
+
+    let start = Instant::now();
+    let results = block_on(predict_concurrently(predictor, data));
+    assert!(
+        start.elapsed() < delay,
+        "flushed after {:?}",
+        start.elapsed()
+    );
+    assert_eq!(results.len(), 8);
+}
+
+#[test]
+fn test_partial_batch_flushed_at_deadline() {
+    let booster = booster();
+    let data = &rows()[..3 * 3];
+    let expected = booster.predict(data, 3, 3, predict_type::NORMAL).unwrap();
+
+    let delay = Duration::from_millis(50);
+    let config = BatchConfig::default()
+        .with_max_batch_size(64)
+        .with_max_delay(delay);
+    let predictor = Arc::new(AsyncPredictor::new(booster, config).unwrap());
+
+    let start = Instant::now();
+    let results = block_on(predict_concurrently(Arc::clone(&predictor), data));
+    assert!(
+        start.elapsed() >= delay,
+        "flushed after {:?}",
+        start.elapsed()
+    );
+    assert_same(&results.concat(), &expected);
+
+    // A later request starts a new batch with its own deadline
+    let start = Instant::now();
+    let result = block_on(predictor.predict(&data[..3])).unwrap();
+    assert!(
+        start.elapsed() >= delay,
+        "flushed after {:?}",
+        start.elapsed()
+    );
+    assert_same(&result, &expected[..3]);
+}
+
+#[test]
+fn test_wrong_row_length_fails_alone() {
+    let booster = booster();
+    let row = [0.25, 1.0, -1.0];
+    let expected = booster.predict(&row, 1, 3, predict_type::NORMAL).unwrap();
+
+    let config = BatchConfig::default().with_max_delay(Duration::from_millis(20));
+    let predictor = Arc::new(AsyncPredictor::new(booster, config).unwrap());
+
+    let (bad, good) = block_on(async {
+        let bad = {
+            let predictor = Arc::clone(&predictor);
+            tokio::spawn(async move { predictor.predict(&[1.0, 2.0]).await })
+        };
+        let good = {
+            let predictor = Arc::clone(&predictor);
+            tokio::spawn(async move { predictor.predict(&row).await })
+        };
+        (bad.await.unwrap(), good.await.unwrap())
+    });
+
+    let err = bad.unwrap_err();
+    assert!(
+        err.description.contains("expected 3"),
+        "{}",
+        err.description
+    );
+    assert_same(&good.unwrap(), &expected);
+}
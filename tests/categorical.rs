@@ -0,0 +1,121 @@
+#![cfg(feature = "ffi")]
+
+use lightgbm_rust::PandasCategorical;
+
+fn model_text(categorical_feature: &str, pandas_categorical: &str) -> String {
+    format!(
+        "tree\nversion=v4\nmax_feature_idx=3\nfeature_names=age country plan city\n\n\
+         end of trees\n\nparameters:\n[categorical_feature: {}]\nend of parameters\n\n\
+         pandas_categorical:{}\n",
+        categorical_feature, pandas_categorical
+    )
+}
+
+#[test]
+fn test_categories_keyed_on_category_columns() {
+    let text = model_text("3,1", r#"[["DE", "FR"], ["a", "bé", "😀"]]"#);
+    let categorical = PandasCategorical::from_model_str(&text).unwrap();
+
+    assert_eq!(categorical.feature_indices().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(categorical.code(1, "FR"), Some(1));
+    assert_eq!(categorical.code(3, "bé"), Some(1));
+    assert_eq!(categorical.code(3, "😀"), Some(2));
+    assert_eq!(categorical.code(3, "DE"), None);
+    assert_eq!(categorical.categories(0), None);
+
+    let named = model_text("name:country,city", r#"[["DE", "FR"], [1, 2]]"#);
+    let categorical = PandasCategorical::from_model_str(&named).unwrap();
+    assert_eq!(categorical.code(3, "2"), Some(1));
+}
+
+#[test]
+fn test_mismatched_categorical_feature_is_ignored() {
+    // categorical_feature also names a non-category column, so the lists cannot be placed
+    let text = model_text("0,1,3", r#"[["DE", "FR"], ["a", "b"]]"#);
+    assert_eq!(PandasCategorical::from_model_str(&text), None);
+
+    let categorical = PandasCategorical::with_columns(&text, &[3, 1])
+        .unwrap()
+        .unwrap();
+    assert_eq!(categorical.code(1, "FR"), Some(1));
+    assert_eq!(categorical.code(3, "b"), Some(1));
+    assert!(PandasCategorical::with_columns(&text, &[1]).is_err());
+
+    for invalid in [r#"[["DE", "FR"]"#, r#"[["\x"]]"#, "{}"] {
+        assert_eq!(
+            PandasCategorical::from_model_str(&model_text("1", invalid)),
+            None
+        );
+    }
+    assert_eq!(
+        PandasCategorical::from_model_str(&model_text("", "null")),
+        None
+    );
+}
+
+#[test]
+fn test_large_category_lists() {
+    let categories: Vec<String> = (0..200_000).map(|i| format!("\"ké{}\"", i)).collect();
+    let text = model_text("1", &format!("[[{}]]", categories.join(", ")));
+    let categorical = PandasCategorical::from_model_str(&text).unwrap();
+    assert_eq!(categorical.categories(1).unwrap().len(), 200_000);
+    assert_eq!(categorical.code(1, "ké199999"), Some(199_999));
+}
+
+#[test]
+fn test_escaped_and_numeric_categories() {
+    let text = model_text(
+        "1,3",
+        r#"[["a\"b", "\\\/\b\f\n\r\t", "\u00e9", "\ud83d\ude00", "\u00FCx"], [1, -2.5, 1e3, 0, true]]"#,
+    );
+    let categorical = PandasCategorical::from_model_str(&text).unwrap();
+    assert_eq!(
+        categorical.categories(1).unwrap(),
+        ["a\"b", "\\/\u{8}\u{c}\n\r\t", "é", "😀", "üx"]
+    );
+    assert_eq!(categorical.code(1, "😀"), Some(3));
+    assert_eq!(
+        categorical.categories(3).unwrap(),
+        ["1", "-2.5", "1e3", "0", "true"]
+    );
+    assert_eq!(categorical.code(3, "-2.5"), Some(1));
+}
+
+#[test]
+fn test_malformed_categories_are_errors() {
+    for invalid in [
+        r#"[["a""#,
+        r#"[["a"]"#,
+        r#"[["a"] ["b"]]"#,
+        r#"[["a"]] x"#,
+        r#"[["a",]]"#,
+        r#"[[abc]]"#,
+        r#"[[01]]"#,
+        r#"[[1.]]"#,
+        r#"[[-]]"#,
+        r#"[[null]]"#,
+        r#"[["\x"]]"#,
+        r#"[["\é"]]"#,
+        r#"[["\u12"]]"#,
+        r#"[["\u+041"]]"#,
+        r#"[["\ud83d"]]"#,
+        r#"[["\ud83dA"]]"#,
+        r#"[["\ude00"]]"#,
+        r#"[["a\"#,
+        "[[\"a",
+        "{}",
+    ] {
+        let text = model_text("1", invalid);
+        assert!(
+            PandasCategorical::with_columns(&text, &[1]).is_err(),
+            "{}",
+            invalid
+        );
+        assert_eq!(
+            PandasCategorical::from_model_str(&text),
+            None,
+            "{}",
+            invalid
+        );
+    }
+}
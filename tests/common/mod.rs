@@ -0,0 +1,229 @@
+//! Model fixtures shared by the integration tests
+#![allow(dead_code)]
+
+/// Trees over 3 features covering default-left, default-right, zero-as-missing,
+/// NaN-as-missing and categorical splits, and linear leaves
+pub const TREES: [&str; 4] = [
+    "num_leaves=3
+num_cat=0
+split_feature=0 2
+split_gain=1 1
+threshold=0.5 0.25
+decision_type=2 10
+left_child=1 -1
+right_child=-2 -3
+leaf_value=-1 2 3
+leaf_weight=1 1 1
+leaf_count=1 1 1
+internal_value=0 0
+internal_weight=0 0
+internal_count=3 2
+is_linear=0
+shrinkage=1
+",
+    "num_leaves=3
+num_cat=0
+split_feature=1 0
+split_gain=1 1
+threshold=1.0000000180025095e-35 -0.5
+decision_type=8 6
+left_child=1 -1
+right_child=-2 -3
+leaf_value=0.5 0.25 -0.125
+leaf_weight=1 1 1
+leaf_count=1 1 1
+internal_value=0 0
+internal_weight=0 0
+internal_count=3 2
+is_linear=0
+shrinkage=1
+",
+    "num_leaves=3
+num_cat=2
+split_feature=0 1
+split_gain=1 1
+threshold=0 1
+decision_type=1 9
+left_child=1 -1
+right_child=-2 -3
+leaf_value=0.75 -0.5 0.125
+leaf_weight=1 1 1
+leaf_count=1 1 1
+internal_value=0 0
+internal_weight=0 0
+internal_count=3 2
+cat_boundaries=0 1 3
+cat_threshold=10 1 2
+is_linear=0
+shrinkage=1
+",
+    "num_leaves=3
+num_cat=0
+split_feature=2 1
+split_gain=1 1
+threshold=0.5 -0.25
+decision_type=2 8
+left_child=1 -1
+right_child=-2 -3
+leaf_value=0.25 -0.75 1.5
+leaf_weight=1 1 1
+leaf_count=1 1 1
+internal_value=0 0
+internal_weight=0 0
+internal_count=3 2
+is_linear=1
+leaf_const=0.125 -1 0.5
+num_features=2 0 1
+leaf_features=0 1   2  
+leaf_coeff=0.5 -0.25   2  
+shrinkage=1
+",
+];
+
+/// Trees over 3 features with thresholds that are not `f32` values, NaN-as-missing,
+/// zero-as-missing (default left and right) and categorical splits
+pub const F32_TREES: [&str; 3] = [
+    "num_leaves=3
+num_cat=0
+split_feature=0 2
+split_gain=1 1
+threshold=0.10000000000000001 -0.30000000000000004
+decision_type=2 10
+left_child=1 -1
+right_child=-2 -3
+leaf_value=-1 2 3
+leaf_weight=1 1 1
+leaf_count=1 1 1
+internal_value=0 0
+internal_weight=0 0
+internal_count=3 2
+is_linear=0
+shrinkage=1
+",
+    "num_leaves=3
+num_cat=1
+split_feature=0 1
+split_gain=1 1
+threshold=0 1.0000000180025095e-35
+decision_type=1 4
+left_child=1 -1
+right_child=-2 -3
+leaf_value=0.75 -0.5 0.125
+leaf_weight=1 1 1
+leaf_count=1 1 1
+internal_value=0 0
+internal_weight=0 0
+internal_count=3 2
+cat_boundaries=0 2
+cat_threshold=10 1
+is_linear=0
+shrinkage=1
+",
+    "num_leaves=2
+num_cat=0
+split_feature=1
+split_gain=1
+threshold=0.5
+decision_type=6
+left_child=-1
+right_child=-2
+leaf_value=0.25 -0.375
+leaf_weight=1 1
+leaf_count=1 1
+internal_value=0
+internal_weight=0
+internal_count=2
+is_linear=0
+shrinkage=1
+",
+];
+
+/// Build a model text with `iterations` rounds of `num_class` trees each, cycling through
+/// `trees`
+pub fn model_text(
+    trees: &[&str],
+    objective: &str,
+    num_class: usize,
+    iterations: usize,
+    average_output: bool,
+) -> String {
+    let blocks: Vec<String> = (0..iterations * num_class)
+        .map(|i| format!("Tree={}\n{}\n\n", i, trees[i % trees.len()]))
+        .collect();
+    let sizes: Vec<String> = blocks.iter().map(|b| b.len().to_string()).collect();
+
+    let mut text = String::from("tree\nversion=v4\n");
+    text += &format!(
+        "num_class={}\nnum_tree_per_iteration={}\n",
+        num_class, num_class
+    );
+    text += "label_index=0\nmax_feature_idx=2\n";
+    text += &format!("objective={}\n", objective);
+    if average_output {
+        text += "average_output\n";
+    }
+    text += "feature_names=a b c\nfeature_infos=[-1:3] [-1:3] [-1:3]\n";
+    text += &format!("tree_sizes={}\n\n", sizes.join(" "));
+    text += &blocks.concat();
+    text += "end of trees\n\nfeature_importances:\na=2\nb=1\nc=1\n";
+    text
+}
+
+/// Every combination of `values` across 3 columns
+pub fn grid<T: Copy>(values: &[T]) -> Vec<T> {
+    let mut data = Vec::new();
+    for &a in values {
+        for &b in values {
+            for &c in values {
+                data.extend_from_slice(&[a, b, c]);
+            }
+        }
+    }
+    data
+}
+
+/// Values around every threshold of [`TREES`]
+pub const VALUES: [f64; 15] = [
+    f64::NAN,
+    0.0,
+    -0.0,
+    1e-36,
+    -1e-36,
+    0.25,
+    0.5,
+    0.75,
+    -0.5,
+    -1.0,
+    2.0,
+    3.9,
+    33.0,
+    1e10,
+    f64::INFINITY,
+];
+
+/// Values around every threshold of [`F32_TREES`], including the `f32` neighbours of
+/// thresholds that are not `f32` values; categorical inputs are whole numbers
+pub const F32_VALUES: [f32; 18] = [
+    f32::NAN,
+    0.0,
+    -0.0,
+    1e-36,
+    -1e-36,
+    0.1,
+    0.099999994,
+    0.10000001,
+    -0.3,
+    -0.29999998,
+    -0.30000004,
+    0.5,
+    1.0,
+    3.0,
+    33.0,
+    -1.0,
+    f32::INFINITY,
+    f32::NEG_INFINITY,
+];
+
+pub fn rows() -> Vec<f64> {
+    grid(&VALUES)
+}
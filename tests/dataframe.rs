@@ -0,0 +1,199 @@
+#![cfg(feature = "polars")]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{predict_type, Booster};
+use polars::prelude::*;
+
+fn booster(objective: &str, num_class: usize) -> Booster {
+    Booster::load_from_string(&model_text(&TREES, objective, num_class, 3, false)).unwrap()
+}
+
+fn column(data: &[f64], index: usize) -> Vec<f64> {
+    data.chunks(3).map(|row| row[index]).collect()
+}
+
+fn values(df: &DataFrame, name: &str) -> Vec<f64> {
+    df.column(name)
+        .unwrap()
+        .f64()
+        .unwrap()
+        .into_no_null_iter()
+        .collect()
+}
+
+/// Compare bit patterns, as some outputs are NaN
+fn assert_same(actual: &[f64], expected: &[f64]) {
+    let bits = |values: &[f64]| values.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    assert_eq!(
+        bits(actual),
+        bits(expected),
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+fn categorical(name: &str, labels: &[Option<&str>]) -> Column {
+    Column::new(name.into(), labels)
+        .cast(&DataType::Categorical(None, CategoricalOrdering::Physical))
+        .unwrap()
+}
+
+#[test]
+fn test_predict_dataframe_matches_predict() {
+    let booster = booster("binary", 1);
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+    let df = DataFrame::new(vec![
+        Column::new("c".into(), column(&data, 2)),
+        Column::new("unused".into(), vec!["x"; num_rows as usize]),
+        Column::new("a".into(), column(&data, 0)),
+        Column::new("b".into(), column(&data, 1)),
+    ])
+    .unwrap();
+
+    let expected = booster
+        .predict(&data, num_rows, 3, predict_type::NORMAL)
+        .unwrap();
+    let predictions = booster
+        .predict_dataframe(&df, predict_type::NORMAL)
+        .unwrap();
+    assert_eq!(predictions.get_column_names(), ["prediction"]);
+    assert_same(&values(&predictions, "prediction"), &expected);
+}
+
+#[test]
+fn test_predict_dataframe_casts_and_nulls() {
+    let booster = booster("regression", 1);
+    let df = DataFrame::new(vec![
+        Column::new("a".into(), [Some(1i32), None, Some(-1)]),
+        Column::new("b".into(), [Some(true), Some(false), None]),
+        Column::new("c".into(), [Some(0.75f32), Some(0.25), None]),
+    ])
+    .unwrap();
+    let data = [
+        1.0,
+        1.0,
+        0.75,
+        f64::NAN,
+        0.0,
+        0.25,
+        -1.0,
+        f64::NAN,
+        f64::NAN,
+    ];
+
+    let expected = booster
+        .predict(&data, 3, 3, predict_type::RAW_SCORE)
+        .unwrap();
+    let predictions = booster
+        .predict_dataframe(&df, predict_type::RAW_SCORE)
+        .unwrap();
+    assert_same(&values(&predictions, "prediction"), &expected);
+}
+
+#[test]
+fn test_predict_dataframe_one_column_per_output() {
+    let booster = booster("multiclass num_class:3", 3);
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+    let df = DataFrame::new(vec![
+        Column::new("a".into(), column(&data, 0)),
+        Column::new("b".into(), column(&data, 1)),
+        Column::new("c".into(), column(&data, 2)),
+    ])
+    .unwrap();
+
+    let expected = booster
+        .predict(&data, num_rows, 3, predict_type::NORMAL)
+        .unwrap();
+    let predictions = booster
+        .predict_dataframe(&df, predict_type::NORMAL)
+        .unwrap();
+    assert_eq!(
+        predictions.get_column_names(),
+        ["prediction_0", "prediction_1", "prediction_2"]
+    );
+    for class in 0..3 {
+        let expected: Vec<f64> = expected.iter().skip(class).step_by(3).copied().collect();
+        assert_same(
+            &values(&predictions, &format!("prediction_{}", class)),
+            &expected,
+        );
+    }
+}
+
+#[test]
+fn test_predict_dataframe_categorical_labels() {
+    let text = model_text(&TREES, "regression", 1, 3, false)
+        + "\nparameters:\n[categorical_feature: 0,1]\nend of parameters\n\n\
+           pandas_categorical:[[\"p\", \"q\", \"r\", \"s\"], [\"u\", \"v\", \"w\"]]\n";
+    let booster = Booster::load_from_string(&text).unwrap();
+    let df = DataFrame::new(vec![
+        categorical(
+            "a",
+            &[Some("q"), Some("s"), Some("p"), Some("unknown"), None],
+        ),
+        categorical(
+            "b",
+            &[Some("w"), Some("u"), Some("v"), Some("u"), Some("v")],
+        ),
+        Column::new("c".into(), [0.0, 0.5, 1.0, 1.5, 2.0]),
+    ])
+    .unwrap();
+    let data = [
+        1.0,
+        2.0,
+        0.0,
+        3.0,
+        0.0,
+        0.5,
+        0.0,
+        1.0,
+        1.0,
+        f64::NAN,
+        0.0,
+        1.5,
+        f64::NAN,
+        1.0,
+        2.0,
+    ];
+
+    let expected = booster
+        .predict(&data, 5, 3, predict_type::RAW_SCORE)
+        .unwrap();
+    let predictions = booster
+        .predict_dataframe(&df, predict_type::RAW_SCORE)
+        .unwrap();
+    assert_same(&values(&predictions, "prediction"), &expected);
+
+    // Without category lists the labels cannot be encoded
+    let err = self::booster("regression", 1)
+        .predict_dataframe(&df, predict_type::RAW_SCORE)
+        .unwrap_err();
+    assert!(err.description.contains("'a'"), "{}", err.description);
+}
+
+#[test]
+fn test_predict_dataframe_rejects_bad_columns() {
+    let booster = booster("regression", 1);
+
+    let missing = DataFrame::new(vec![Column::new("b".into(), [1.0])]).unwrap();
+    let err = booster
+        .predict_dataframe(&missing, predict_type::NORMAL)
+        .unwrap_err();
+    assert!(err.description.contains("a, c"), "{}", err.description);
+
+    let strings = DataFrame::new(vec![
+        Column::new("a".into(), ["1"]),
+        Column::new("b".into(), [1.0]),
+        Column::new("c".into(), [1.0]),
+    ])
+    .unwrap();
+    let err = booster
+        .predict_dataframe(&strings, predict_type::NORMAL)
+        .unwrap_err();
+    assert!(err.description.contains("'a'"), "{}", err.description);
+}
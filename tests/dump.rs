@@ -0,0 +1,147 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{
+    predict_type, Booster, DecisionType, MissingType, ModelDump, Node, SaveOptions, SplitNode,
+    Threshold,
+};
+
+fn booster(trees: &[&str], iterations: usize) -> Booster {
+    Booster::load_from_string(&model_text(trees, "regression", 1, iterations, false)).unwrap()
+}
+
+fn split(node: &Node) -> &SplitNode {
+    match node {
+        Node::Split(split) => split,
+        Node::Leaf(_) => panic!("expected a split, got {:?}", node),
+    }
+}
+
+#[test]
+fn test_dump_header_and_splits() {
+    let dump = booster(&TREES, 1).dump().unwrap();
+
+    assert_eq!(dump.name, "tree");
+    assert_eq!(dump.num_class, 1);
+    assert_eq!(dump.num_tree_per_iteration, 1);
+    assert_eq!(dump.max_feature_idx, 2);
+    assert_eq!(dump.feature_names, ["a", "b", "c"]);
+    assert_eq!(dump.objective.as_deref(), Some("regression"));
+    assert_eq!(dump.tree_info.len(), 1);
+
+    let tree = &dump.tree_info[0];
+    assert_eq!((tree.tree_index, tree.num_leaves, tree.num_cat), (0, 3, 0));
+    assert_eq!(tree.tree_structure.depth(), 2);
+
+    let root = split(&tree.tree_structure);
+    assert_eq!(root.split_feature, 0);
+    assert_eq!(root.threshold, Threshold::Numerical(0.5));
+    assert_eq!(root.decision_type, DecisionType::LessOrEqual);
+    assert!(root.default_left);
+    assert_eq!(root.missing_type, MissingType::None);
+    assert!(root.right_child.is_leaf());
+
+    let left = split(&root.left_child);
+    assert_eq!(left.split_feature, 2);
+    assert_eq!(left.threshold, Threshold::Numerical(0.25));
+    assert_eq!(left.missing_type, MissingType::NaN);
+
+    let leaves: Vec<(usize, f64)> = tree
+        .tree_structure
+        .leaves()
+        .iter()
+        .map(|leaf| (leaf.leaf_index, leaf.leaf_value))
+        .collect();
+    assert_eq!(leaves, [(0, -1.0), (2, 3.0), (1, 2.0)]);
+}
+
+#[test]
+fn test_dump_categorical_and_linear_trees() {
+    let dump = booster(&TREES, 4).dump().unwrap();
+    let categorical = &dump.tree_info[2];
+    assert_eq!(categorical.num_cat, 2);
+    let root = split(&categorical.tree_structure);
+    assert_eq!(root.decision_type, DecisionType::Equal);
+    assert_eq!(root.threshold, Threshold::Categorical(vec![1, 3]));
+    let left = split(&root.left_child);
+    assert_eq!(left.threshold, Threshold::Categorical(vec![0, 33]));
+    assert_eq!(left.missing_type, MissingType::NaN);
+
+    let linear = &dump.tree_info[3];
+    let leaves = linear.tree_structure.leaves();
+    let first = leaves.iter().find(|leaf| leaf.leaf_index == 0).unwrap();
+    assert_eq!(first.leaf_const, Some(0.125));
+    assert_eq!(first.leaf_features.as_deref(), Some(&[0, 1][..]));
+    assert_eq!(first.leaf_coeff.as_deref(), Some(&[0.5, -0.25][..]));
+    assert!(dump.tree_info[0].tree_structure.leaves()[0]
+        .leaf_const
+        .is_none());
+}
+
+#[test]
+fn test_dump_leaves_match_predictions() {
+    // Without linear leaves, a raw score is the sum of the leaves the row lands in
+    let booster = booster(&TREES[..3], 2);
+    let dump = booster.dump().unwrap();
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+
+    let leaf_values: Vec<Vec<f64>> = dump
+        .tree_info
+        .iter()
+        .map(|tree| {
+            let mut values = vec![0.0; tree.num_leaves];
+            for leaf in tree.tree_structure.leaves() {
+                values[leaf.leaf_index] = leaf.leaf_value;
+            }
+            values
+        })
+        .collect();
+
+    let leaves = booster
+        .predict(&data, num_rows, 3, predict_type::LEAF_INDEX)
+        .unwrap();
+    let raw = booster
+        .predict(&data, num_rows, 3, predict_type::RAW_SCORE)
+        .unwrap();
+    for (row, expected) in leaves.chunks(leaf_values.len()).zip(raw) {
+        let sum: f64 = row
+            .iter()
+            .zip(&leaf_values)
+            .map(|(&leaf, values)| values[leaf as usize])
+            .sum();
+        assert!((sum - expected).abs() < 1e-12, "{} != {}", sum, expected);
+    }
+}
+
+#[test]
+fn test_dump_iterations() {
+    let booster = booster(&TREES, 4);
+    assert_eq!(booster.dump().unwrap().tree_info.len(), 4);
+
+    let options = SaveOptions::default()
+        .with_start_iteration(1)
+        .with_num_iteration(2);
+    let dump = booster.dump_with_options(&options).unwrap();
+    assert_eq!(dump.tree_info.len(), 2);
+    assert_eq!(dump.tree_info[0].num_cat, 0);
+    assert_eq!(dump.tree_info[1].num_cat, 2);
+}
+
+#[test]
+fn test_dump_serde_round_trip() {
+    let booster = booster(&TREES, 4);
+    let dump = booster.dump().unwrap();
+
+    let json = serde_json::to_string(&dump).unwrap();
+    assert!(json.contains(r#""threshold":"1||3""#));
+    assert!(json.contains(r#""decision_type":"==""#));
+    let reparsed: ModelDump = serde_json::from_str(&json).unwrap();
+    assert_eq!(reparsed, dump);
+
+    let raw = booster.dump_json(&SaveOptions::default()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&raw).unwrap();
+    assert_eq!(parsed["tree_info"].as_array().unwrap().len(), 4);
+}
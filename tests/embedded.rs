@@ -0,0 +1,88 @@
+#![cfg(all(feature = "native", feature = "embedded"))]
+
+mod common;
+
+use common::{grid, model_text, F32_TREES, F32_VALUES, TREES};
+use lightgbm_rust::{predict_type, CompactBooster, NativeBooster};
+
+#[test]
+fn test_compact_matches_native() {
+    // 3.9 checks that non-whole categories are truncated
+    let data = grid(&[&F32_VALUES[..], &[3.9]].concat());
+    let num_rows = data.len() / 3;
+    for (objective, num_class) in [
+        ("regression", 1),
+        ("binary sigmoid:0.5", 1),
+        ("multiclass num_class:3", 3),
+    ] {
+        let native = NativeBooster::load_from_string(&model_text(
+            &F32_TREES, objective, num_class, 3, false,
+        ))
+        .unwrap();
+        let compact = CompactBooster::from_bytes(&native.to_compact().unwrap()).unwrap();
+        assert_eq!(compact.num_features(), 3);
+        assert_eq!(compact.num_outputs(), num_class);
+        assert_eq!(compact.num_trees(), 3 * num_class);
+
+        for (predict_type, raw) in [
+            (predict_type::NORMAL, false),
+            (predict_type::RAW_SCORE, true),
+        ] {
+            let expected = native
+                .predict(&data, num_rows as i32, 3, predict_type)
+                .unwrap();
+            let mut output = vec![0.0; num_class];
+            for (row, expected) in data.chunks_exact(3).zip(expected.chunks_exact(num_class)) {
+                if raw {
+                    compact.predict_raw(row, &mut output).unwrap();
+                } else {
+                    compact.predict(row, &mut output).unwrap();
+                }
+                assert_eq!(output, expected, "{} row {:?}", objective, row);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_compact_rejects_invalid_input() {
+    let native =
+        NativeBooster::load_from_string(&model_text(&F32_TREES, "regression", 1, 1, false))
+            .unwrap();
+    let bytes = native.to_compact().unwrap();
+    let compact = CompactBooster::from_bytes(&bytes).unwrap();
+
+    let mut output = [0.0];
+    assert!(compact.predict(&[0.0; 2], &mut output).is_err());
+    assert!(compact.predict(&[0.0; 3], &mut [0.0; 2]).is_err());
+
+    for len in 0..bytes.len() {
+        assert!(CompactBooster::from_bytes(&bytes[..len]).is_err());
+    }
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(CompactBooster::from_bytes(&trailing).is_err());
+
+    // Counts of trees (offset 26) and categorical bitsets (offset 30) from a hostile source
+    for offset in [26, 30] {
+        for count in [u32::MAX, u32::MAX - 1, 1 << 30] {
+            let mut hostile = bytes.clone();
+            hostile[offset..offset + 4].copy_from_slice(&count.to_le_bytes());
+            assert!(CompactBooster::from_bytes(&hostile).is_err());
+        }
+    }
+    for seed in 0..bytes.len() {
+        let mut corrupt = bytes.clone();
+        corrupt[seed] ^= 0xa5;
+        // Any result is fine as long as nothing panics
+        if let Ok(compact) = CompactBooster::from_bytes(&corrupt) {
+            let _ = compact.predict(&[0.5, f32::NAN, 3.0], &mut output);
+        }
+    }
+
+    // The last of TREES has linear leaves
+    let linear =
+        NativeBooster::load_from_string(&model_text(&TREES[3..], "regression", 1, 1, false))
+            .unwrap();
+    assert!(linear.to_compact().is_err());
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "ffi")]
+
+mod common;
+
+use common::{model_text, TREES};
+use lightgbm_rust::{Booster, ImportanceType};
+
+/// [`TREES`] with a gain of 0.5 on the first split and 2 on the second one, plus a tree
+/// whose only split has no gain and so does not count
+fn booster() -> Booster {
+    let mut trees: Vec<String> = TREES
+        .iter()
+        .map(|tree| tree.replace("split_gain=1 1", "split_gain=0.5 2"))
+        .collect();
+    trees.push(TREES[0].replace("split_gain=1 1", "split_gain=0 0"));
+    let trees: Vec<&str> = trees.iter().map(String::as_str).collect();
+    Booster::load_from_string(&model_text(&trees, "regression", 1, 5, false)).unwrap()
+}
+
+#[test]
+fn test_split_and_gain_importance() {
+    let booster = booster();
+
+    let split = booster
+        .feature_importance(ImportanceType::Split, -1)
+        .unwrap();
+    assert_eq!(split.importance_type(), ImportanceType::Split);
+    assert_eq!(split.values(), [3.0, 3.0, 2.0]);
+
+    let gain = booster.feature_importance(ImportanceType::Gain, 0).unwrap();
+    assert_eq!(gain.importance_type(), ImportanceType::Gain);
+    assert_eq!(gain.values(), [3.0, 4.5, 2.5]);
+    assert_eq!(gain.get("b"), Some(4.5));
+    assert_eq!(gain.get("d"), None);
+    assert_eq!(
+        gain.iter().collect::<Vec<_>>(),
+        [("a", 3.0), ("b", 4.5), ("c", 2.5)]
+    );
+}
+
+#[test]
+fn test_importance_of_first_iterations() {
+    let booster = booster();
+
+    let split = booster
+        .feature_importance(ImportanceType::Split, 1)
+        .unwrap();
+    assert_eq!(split.values(), [1.0, 0.0, 1.0]);
+
+    let gain = booster.feature_importance(ImportanceType::Gain, 2).unwrap();
+    assert_eq!(gain.values(), [2.5, 0.5, 2.0]);
+}
+
+#[test]
+fn test_sorted_and_normalized() {
+    let booster = booster();
+
+    let split = booster
+        .feature_importance(ImportanceType::Split, -1)
+        .unwrap();
+    // Ties keep model column order
+    assert_eq!(split.sorted(), [("a", 3.0), ("b", 3.0), ("c", 2.0)]);
+
+    let gain = booster
+        .feature_importance(ImportanceType::Gain, -1)
+        .unwrap()
+        .normalized();
+    assert_eq!(gain.sorted(), [("b", 0.45), ("a", 0.3), ("c", 0.25)]);
+    assert_eq!(gain.importance_type(), ImportanceType::Gain);
+
+    // Only the tree without gain, so nothing to normalize
+    let text = model_text(
+        &[&TREES[0].replace("split_gain=1 1", "split_gain=0 0")],
+        "regression",
+        1,
+        1,
+        false,
+    );
+    let unused = Booster::load_from_string(&text)
+        .unwrap()
+        .feature_importance(ImportanceType::Gain, -1)
+        .unwrap()
+        .normalized();
+    assert_eq!(unused.values(), [0.0, 0.0, 0.0]);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "ffi")]
+
+use lightgbm_rust::{predict_type, Booster, SaveOptions};
+
+/// Two trees: a stump with 2 leaves and a tree with 3 leaves
+const MODEL: &str = "tree
+version=v4
+num_class=1
+num_tree_per_iteration=1
+label_index=0
+max_feature_idx=1
+objective=regression
+feature_names=a b
+feature_infos=[-1:3] [-1:3]
+tree_sizes=243 279
+
+Tree=0
+num_leaves=2
+num_cat=0
+split_feature=0
+split_gain=1
+threshold=0.5
+decision_type=2
+left_child=-1
+right_child=-2
+leaf_value=-1 2
+leaf_weight=1 1
+leaf_count=1 1
+internal_value=0
+internal_weight=0
+internal_count=2
+is_linear=0
+shrinkage=1
+
+
+Tree=1
+num_leaves=3
+num_cat=0
+split_feature=1 0
+split_gain=1 1
+threshold=0.5 1.5
+decision_type=2 2
+left_child=-1 -2
+right_child=1 -3
+leaf_value=0.5 0.25 -0.125
+leaf_weight=1 1 1
+leaf_count=1 1 1
+internal_value=0 0
+internal_weight=0 0
+internal_count=3 2
+is_linear=0
+shrinkage=1
+
+
+end of trees
+";
+
+#[test]
+fn test_leaf_bounds() {
+    let booster = Booster::load_from_string(MODEL).unwrap();
+    assert_eq!(booster.num_leaves(0).unwrap(), 2);
+    assert_eq!(booster.num_leaves(1).unwrap(), 3);
+    assert!(booster.num_leaves(2).is_err());
+    assert!(booster.num_leaves(-1).is_err());
+
+    assert_eq!(booster.get_leaf_value(1, 2).unwrap(), -0.125);
+    assert!(booster.get_leaf_value(0, 2).is_err());
+    assert!(booster.get_leaf_value(1, -1).is_err());
+    assert!(booster.get_leaf_value(2, 0).is_err());
+}
+
+#[test]
+fn test_set_leaf_value() {
+    let mut booster = Booster::load_from_string(MODEL).unwrap();
+    booster.set_leaf_value(0, 1, 4.0).unwrap();
+    assert_eq!(booster.get_leaf_value(0, 1).unwrap(), 4.0);
+    assert!(booster.set_leaf_value(1, 3, 0.0).is_err());
+
+    let prediction = booster
+        .predict(&[1.0f64, 0.0], 1, 2, predict_type::NORMAL)
+        .unwrap();
+    assert_eq!(prediction, [4.5]);
+
+    let saved = booster
+        .save_model_to_string(&SaveOptions::default())
+        .unwrap();
+    let reloaded = Booster::load_from_string(&saved).unwrap();
+    assert_eq!(reloaded.get_leaf_value(0, 1).unwrap(), 4.0);
+    assert_eq!(reloaded.num_leaves(1).unwrap(), 3);
+}
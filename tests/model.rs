@@ -0,0 +1,213 @@
+#![cfg(feature = "ffi")]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{predict_type, Booster, FeatureNameDiff, SaveOptions};
+use std::fs;
+
+const PANDAS_CATEGORICAL: &str = r#"[["p", "q", "r", "s"], ["u", "v", "w"]]"#;
+
+fn model(num_class: usize, iterations: usize) -> String {
+    let objective = if num_class == 1 {
+        "binary".to_string()
+    } else {
+        format!("multiclass num_class:{}", num_class)
+    };
+    model_text(&TREES, &objective, num_class, iterations, false)
+}
+
+fn categorical_model() -> String {
+    model(1, 3)
+        + "\nparameters:\n[categorical_feature: 0,1]\nend of parameters\n\n\
+           pandas_categorical:"
+        + PANDAS_CATEGORICAL
+        + "\n"
+}
+
+/// Compare bit patterns, as some outputs are NaN
+fn assert_same(actual: &[f64], expected: &[f64]) {
+    let bits = |values: &[f64]| values.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    assert_eq!(bits(actual), bits(expected));
+}
+
+fn predictions(booster: &Booster) -> Vec<f64> {
+    let data = rows();
+    booster
+        .predict(&data, (data.len() / 3) as i32, 3, predict_type::RAW_SCORE)
+        .unwrap()
+}
+
+#[test]
+fn test_save_model_to_string_round_trip() {
+    let booster = Booster::load_from_string(&model(3, 4)).unwrap();
+
+    let saved = booster
+        .save_model_to_string(&SaveOptions::default())
+        .unwrap();
+    let reloaded = Booster::load_from_string(&saved).unwrap();
+    assert_eq!(reloaded.num_trees().unwrap(), 12);
+    assert_same(&predictions(&reloaded), &predictions(&booster));
+}
+
+#[test]
+fn test_save_model_iterations() {
+    let booster = Booster::load_from_string(&model(3, 4)).unwrap();
+
+    for (start, num, trees) in [(0, 1, 3), (0, 2, 6), (1, 2, 6), (3, -1, 3), (0, 10, 12)] {
+        let options = SaveOptions::default()
+            .with_start_iteration(start)
+            .with_num_iteration(num);
+        let saved = booster.save_model_to_string(&options).unwrap();
+        let reloaded = Booster::load_from_string(&saved).unwrap();
+        assert_eq!(
+            reloaded.num_trees().unwrap(),
+            trees,
+            "start {} num {}",
+            start,
+            num
+        );
+    }
+}
+
+#[test]
+fn test_save_model_to_file() {
+    let booster = Booster::load_from_string(&categorical_model()).unwrap();
+    let path = std::env::temp_dir().join(format!("lightgbm-rust-save-{}.txt", std::process::id()));
+
+    booster.save_model(&path, &SaveOptions::default()).unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    let reloaded = Booster::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(text
+        .trim_end()
+        .ends_with(&format!("pandas_categorical:{}", PANDAS_CATEGORICAL)));
+    assert_eq!(reloaded.pandas_categorical(), booster.pandas_categorical());
+    assert_same(&predictions(&reloaded), &predictions(&booster));
+
+    let missing_dir = std::env::temp_dir()
+        .join("lightgbm-rust-no-such-dir")
+        .join("model.txt");
+    assert!(booster
+        .save_model(&missing_dir, &SaveOptions::default())
+        .is_err());
+}
+
+#[test]
+fn test_save_model_to_string_keeps_pandas_categorical() {
+    let booster = Booster::load_from_string(&categorical_model()).unwrap();
+    assert!(booster.pandas_categorical().is_some());
+
+    let saved = booster
+        .save_model_to_string(&SaveOptions::default())
+        .unwrap();
+    assert_eq!(saved.matches("pandas_categorical:").count(), 1);
+    let reloaded = Booster::load_from_string(&saved).unwrap();
+    assert_eq!(reloaded.pandas_categorical(), booster.pandas_categorical());
+    assert_eq!(
+        reloaded
+            .save_model_to_string(&SaveOptions::default())
+            .unwrap(),
+        saved
+    );
+}
+
+#[test]
+fn test_feature_names() {
+    let booster = Booster::load_from_string(&model(1, 1)).unwrap();
+    assert_eq!(booster.feature_names().unwrap(), ["a", "b", "c"]);
+}
+
+#[test]
+fn test_long_feature_names() {
+    // Longer than the initial 256-byte buffer, so the names are read a second time
+    for len in [255, 256, 300, 5000] {
+        let long = "x".repeat(len);
+        let text = model(1, 1).replace(
+            "feature_names=a b c",
+            &format!("feature_names=a {} c", long),
+        );
+        let booster = Booster::load_from_string(&text).unwrap();
+        assert_eq!(booster.feature_names().unwrap(), ["a", long.as_str(), "c"]);
+    }
+}
+
+#[test]
+fn test_validate_feature_names() {
+    let booster = Booster::load_from_string(&model(1, 1)).unwrap();
+    assert!(booster
+        .validate_feature_names(&["a", "b", "c"])
+        .unwrap()
+        .is_match());
+
+    let diff = booster.validate_feature_names(&["b", "a", "d"]).unwrap();
+    assert_eq!(
+        diff,
+        FeatureNameDiff {
+            missing: vec!["c".to_string()],
+            extra: vec!["d".to_string()],
+            misordered: vec![("a".to_string(), 0, 1), ("b".to_string(), 1, 0)],
+        }
+    );
+    assert!(!diff.is_match());
+
+    let diff = booster
+        .validate_feature_names(&["a", "b", "c", "d"])
+        .unwrap();
+    assert_eq!(diff.extra, ["d"]);
+    assert!(diff.missing.is_empty() && diff.misordered.is_empty());
+
+    let diff = booster.validate_feature_names(&["a", "b"]).unwrap();
+    assert_eq!(diff.missing, ["c"]);
+
+    assert!(booster.validate_feature_names(&["a", "b\0", "c"]).is_err());
+}
+
+#[test]
+fn test_structure_accessors() {
+    let booster = Booster::load_from_string(&model(3, 2)).unwrap();
+    assert_eq!(booster.num_features().unwrap(), 3);
+    assert_eq!(booster.num_classes().unwrap(), 3);
+    assert_eq!(booster.num_model_per_iteration().unwrap(), 3);
+    assert_eq!(booster.num_trees().unwrap(), 6);
+    assert_eq!(booster.current_iteration().unwrap(), 2);
+
+    let booster = Booster::load_from_string(&model(1, 5)).unwrap();
+    assert_eq!(booster.num_classes().unwrap(), 1);
+    assert_eq!(booster.num_model_per_iteration().unwrap(), 1);
+    assert_eq!(booster.num_trees().unwrap(), 5);
+    assert_eq!(booster.current_iteration().unwrap(), 5);
+}
+
+#[test]
+fn test_is_linear() {
+    let constant = model_text(&TREES[..3], "regression", 1, 2, false);
+    assert!(!Booster::load_from_string(&constant)
+        .unwrap()
+        .is_linear()
+        .unwrap());
+
+    let linear = model_text(&TREES[3..], "regression", 1, 2, false)
+        + "\nparameters:\n[linear_tree: 1]\nend of parameters\n";
+    assert!(Booster::load_from_string(&linear)
+        .unwrap()
+        .is_linear()
+        .unwrap());
+}
+
+#[test]
+fn test_bounds() {
+    let text = model_text(&TREES[..3], "regression", 1, 3, false);
+    let booster = Booster::load_from_string(&text).unwrap();
+
+    // Sums of the largest and smallest leaf of each tree
+    let upper = booster.upper_bound().unwrap();
+    let lower = booster.lower_bound().unwrap();
+    assert_eq!(upper, 3.0 + 0.5 + 0.75);
+    assert_eq!(lower, -1.0 - 0.125 - 0.5);
+
+    for score in predictions(&booster) {
+        assert!(lower <= score && score <= upper, "{}", score);
+    }
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "std")]
+
+use lightgbm_rust::model_format::{FeatureInfo, TextModel, TreeModel};
+
+/// A categorical tree with a split gain that needs 17 significant digits
+const CATEGORICAL_TREE: &str = "num_leaves=3
+num_cat=1
+split_feature=2 0
+split_gain=12.345678901234567 0.5
+threshold=0 0.10000000000000001
+decision_type=1 2
+left_child=1 -1
+right_child=-2 -3
+leaf_value=0.75 -0.5 0.125
+leaf_weight=10 5.5 4
+leaf_count=10 6 4
+internal_value=0 0.25
+internal_weight=19.5 14
+internal_count=20 14
+cat_boundaries=0 2
+cat_threshold=10 1
+is_linear=0
+shrinkage=0.1
+";
+
+/// A linear tree, with a leaf without features
+const LINEAR_TREE: &str = "num_leaves=2
+num_cat=0
+split_feature=1
+split_gain=1
+threshold=-0.25
+decision_type=8
+left_child=-1
+right_child=-2
+leaf_value=0.25 -0.75
+leaf_weight=1 1
+leaf_count=1 1
+internal_value=0
+internal_weight=2
+internal_count=2
+is_linear=1
+leaf_const=0.125 -1
+num_features=2 0
+leaf_features=0 1   
+leaf_coeff=0.5 -0.25   
+shrinkage=0.1
+";
+
+/// A model laid out as LightGBM and the Python package write it
+fn model_text(trees: &[&str], trailer: &str) -> String {
+    let blocks: Vec<String> = trees
+        .iter()
+        .enumerate()
+        .map(|(i, tree)| format!("Tree={}\n{}\n\n", i, tree))
+        .collect();
+    let sizes: Vec<String> = blocks.iter().map(|b| b.len().to_string()).collect();
+    format!(
+        "tree\nversion=v4\nnum_class=1\nnum_tree_per_iteration=1\nlabel_index=0\n\
+         max_feature_idx=2\nobjective=binary sigmoid:1\nfeature_names=age income country\n\
+         feature_infos=[18:90] [0:1000000] 0:1:2\ntree_sizes={}\n\n{}end of trees\n\n\
+         feature_importances:\ncountry=1234567\nage=12\nincome=3\n{}",
+        sizes.join(" "),
+        blocks.concat(),
+        trailer
+    )
+}
+
+const PARAMETERS: &str = "\nparameters:\n[boosting: gbdt]\n[objective: binary]\n\
+                          [categorical_feature: 2]\n[linear_tree: 1]\n\nend of parameters\n";
+
+#[test]
+fn test_round_trip_is_byte_identical() {
+    for (trees, trailer) in [
+        (&[CATEGORICAL_TREE][..], ""),
+        (&[CATEGORICAL_TREE, LINEAR_TREE][..], PARAMETERS),
+        (
+            &[LINEAR_TREE, CATEGORICAL_TREE][..],
+            &format!(
+                "{}\npandas_categorical:[[\"DE\", \"FR\", \"\\u00e9\"]]\n",
+                PARAMETERS
+            )[..],
+        ),
+    ] {
+        let text = model_text(trees, trailer);
+        let model = TextModel::parse(&text).unwrap();
+        assert_eq!(model.to_string(), text);
+    }
+}
+
+#[test]
+fn test_round_trip_of_parsed_values() {
+    let text = model_text(&[CATEGORICAL_TREE, LINEAR_TREE], PARAMETERS);
+    let model = TextModel::parse(&text).unwrap();
+    assert_eq!(
+        model.feature_importances[0],
+        ("country".to_string(), 1234567.0)
+    );
+    assert!(model.trees[1].is_linear);
+    assert_eq!(model.trees[0].num_cat, 1);
+
+    // Edited values are written in LightGBM's format and parse back to the same model
+    let mut edited = model.clone();
+    edited.trees[0].leaf_value[1] = 0.1;
+    edited.trees[1].leaf_coeff[0][1] = 1.0 / 3.0;
+    edited.feature_importances[0].1 = 2e6;
+    let written = edited.to_string();
+    assert!(written.contains("leaf_value=0.75 0.10000000000000001 0.125\n"));
+    assert!(written.contains("country=2000000\n"));
+    let reparsed = TextModel::parse(&written).unwrap();
+    assert_eq!(reparsed.to_string(), written);
+    // Only the tree sizes, as read, differ from the edited model
+    edited.header.tree_sizes = reparsed.header.tree_sizes.clone();
+    assert_eq!(reparsed, edited);
+    assert_ne!(reparsed, model);
+}
+
+#[test]
+fn test_model_built_from_scratch() {
+    let mut model = TextModel::default();
+    model.header.max_feature_idx = 0;
+    model.header.objective = Some("regression".to_string());
+    model.header.feature_names = vec!["x".to_string()];
+    model.header.feature_infos = vec![FeatureInfo::Numerical { min: 0.0, max: 1.0 }];
+    let mut tree = TreeModel::default();
+    tree.num_leaves = 2;
+    tree.split_feature = vec![0];
+    tree.split_gain = vec![1.0];
+    tree.threshold = vec![0.5];
+    tree.decision_type = vec![2];
+    tree.left_child = vec![-1];
+    tree.right_child = vec![-2];
+    tree.leaf_value = vec![-1.0, 1.0];
+    tree.leaf_weight = vec![1.0, 1.0];
+    tree.leaf_count = vec![1, 1];
+    tree.internal_value = vec![0.0];
+    tree.internal_weight = vec![2.0];
+    tree.internal_count = vec![2];
+    tree.shrinkage = 1.0;
+    model.trees.push(tree);
+
+    let text = model.to_string();
+    assert!(text.starts_with("tree\nversion=v4\nnum_class=1\n"));
+    let mut parsed = TextModel::parse(&text).unwrap();
+    assert_eq!(parsed.header.tree_sizes.len(), 1);
+    parsed.header.tree_sizes.clear();
+    assert_eq!(parsed, model);
+    assert_eq!(parsed.to_string(), text);
+}
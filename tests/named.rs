@@ -0,0 +1,93 @@
+#![cfg(feature = "ffi")]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{predict_type, Booster, UnknownFeatures};
+use std::collections::{BTreeMap, HashMap};
+
+fn booster() -> Booster {
+    Booster::load_from_string(&model_text(&TREES, "binary", 1, 3, false)).unwrap()
+}
+
+#[test]
+fn test_predict_named_matches_predict() {
+    let booster = booster();
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+    let expected = booster
+        .predict(&data, num_rows, 3, predict_type::NORMAL)
+        .unwrap();
+
+    let maps: Vec<HashMap<&str, f64>> = data
+        .chunks(3)
+        .map(|row| HashMap::from([("c", row[2]), ("a", row[0]), ("b", row[1])]))
+        .collect();
+    let actual = booster
+        .predict_named(&maps, predict_type::NORMAL, UnknownFeatures::Error)
+        .unwrap();
+    assert_eq!(actual, expected);
+
+    let owned: Vec<BTreeMap<String, f64>> = maps
+        .iter()
+        .map(|row| row.iter().map(|(k, &v)| (k.to_string(), v)).collect())
+        .collect();
+    let actual = booster
+        .predict_named(owned, predict_type::NORMAL, UnknownFeatures::Error)
+        .unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_predict_named_absent_features_are_missing() {
+    let booster = booster();
+    let rows = [vec![("b", 1.0)], vec![], vec![("c", 0.75), ("a", -1.0)]];
+    let data = [
+        f64::NAN,
+        1.0,
+        f64::NAN,
+        f64::NAN,
+        f64::NAN,
+        f64::NAN,
+        -1.0,
+        f64::NAN,
+        0.75,
+    ];
+
+    let expected = booster
+        .predict(&data, 3, 3, predict_type::RAW_SCORE)
+        .unwrap();
+    let actual = booster
+        .predict_named(rows, predict_type::RAW_SCORE, UnknownFeatures::Error)
+        .unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_predict_named_unknown_features() {
+    let booster = booster();
+    let rows = [
+        vec![("a", 0.25), ("typo", 1.0), ("b", 2.0)],
+        vec![("extra", 3.0), ("typo", 4.0), ("c", 1.0)],
+    ];
+
+    let err = booster
+        .predict_named(
+            rows.clone(),
+            predict_type::RAW_SCORE,
+            UnknownFeatures::Error,
+        )
+        .unwrap_err();
+    assert_eq!(err.description, "Unknown features: typo, extra");
+
+    let known = [vec![("a", 0.25), ("b", 2.0)], vec![("c", 1.0)]];
+    let expected = booster
+        .predict_named(known, predict_type::RAW_SCORE, UnknownFeatures::Error)
+        .unwrap();
+    for unknown in [UnknownFeatures::Warn, UnknownFeatures::Ignore] {
+        let actual = booster
+            .predict_named(rows.clone(), predict_type::RAW_SCORE, unknown)
+            .unwrap();
+        assert_eq!(actual, expected, "{:?}", unknown);
+    }
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "native")]
+
+mod common;
+
+use common::{model_text, rows, TREES, VALUES};
+use lightgbm_rust::{predict_type, NativeBooster};
+
+#[test]
+fn test_rejects_wrong_number_of_columns() {
+    let native =
+        NativeBooster::load_from_string(&model_text(&TREES, "regression", 1, 1, false)).unwrap();
+    assert!(native
+        .predict(&[0.0f64; 4], 1, 4, predict_type::NORMAL)
+        .is_err());
+    assert!(native
+        .predict(&[0.0f64; 4], 2, 3, predict_type::NORMAL)
+        .is_err());
+}
+
+#[test]
+fn test_interactions_sum_to_contrib() {
+    let text = model_text(&TREES[..3], "multiclass num_class:3", 3, 2, false);
+    let native = NativeBooster::load_from_string(&text).unwrap();
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+
+    let contrib = native
+        .predict(&data, num_rows, 3, predict_type::CONTRIB)
+        .unwrap();
+    let interactions = native.predict_interactions(&data, num_rows, 3).unwrap();
+    assert_eq!(interactions.len(), contrib.len() * 4);
+
+    for (matrix, phi) in interactions.chunks_exact(16).zip(contrib.chunks_exact(4)) {
+        for i in 0..4 {
+            let row_sum: f64 = matrix[i * 4..(i + 1) * 4].iter().sum();
+            assert!((row_sum - phi[i]).abs() < 1e-9, "{} vs {}", row_sum, phi[i]);
+            for j in 0..4 {
+                assert!((matrix[i * 4 + j] - matrix[j * 4 + i]).abs() < 1e-9);
+            }
+        }
+    }
+
+    let linear = model_text(&TREES, "regression", 1, TREES.len(), false);
+    let native = NativeBooster::load_from_string(&linear).unwrap();
+    assert!(native.predict_interactions(&data, num_rows, 3).is_err());
+}
+
+#[test]
+fn test_batch_predictor() {
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+    for (objective, num_class) in [("binary sigmoid:1", 1), ("multiclass num_class:3", 3)] {
+        let text = model_text(&TREES, objective, num_class, 3, false);
+        let native = NativeBooster::load_from_string(&text).unwrap();
+        let batch = native.batch_predictor();
+
+        for predict_type in [
+            predict_type::NORMAL,
+            predict_type::RAW_SCORE,
+            predict_type::LEAF_INDEX,
+        ] {
+            let expected = native.predict(&data, num_rows, 3, predict_type).unwrap();
+            let actual = batch.predict(&data, num_rows, 3, predict_type).unwrap();
+            assert_eq!(expected.len(), actual.len());
+            for (e, a) in expected.iter().zip(&actual) {
+                assert!(e == a || (e.is_nan() && a.is_nan()), "{} vs {}", e, a);
+            }
+        }
+        assert!(batch
+            .predict(&data, num_rows, 3, predict_type::CONTRIB)
+            .is_err());
+    }
+}
+
+/// A tree grown leaf-wise by splitting pseudo-random leaves, with thresholds taken from
+/// [`VALUES`] and every numerical decision type
+fn leaf_wise_tree(num_leaves: usize, seed: u64) -> String {
+    let mut state = seed;
+    let mut next = |n: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize % n
+    };
+    let thresholds: Vec<f64> = VALUES.iter().copied().filter(|v| v.is_finite()).collect();
+    let join = |v: Vec<String>| v.join(" ");
+
+    let (mut feature, mut threshold, mut decision_type) = (vec![], vec![], vec![]);
+    let (mut left, mut right) = (Vec::<i32>::new(), Vec::<i32>::new());
+    // Node pointing to each leaf, and whether through its left child
+    let mut parent: Vec<Option<(usize, bool)>> = vec![None];
+    for node in 0..num_leaves - 1 {
+        let leaf = next(parent.len());
+        match parent[leaf] {
+            Some((p, true)) => left[p] = node as i32,
+            Some((p, false)) => right[p] = node as i32,
+            None => {}
+        }
+        feature.push(next(3).to_string());
+        threshold.push(format!("{:e}", thresholds[next(thresholds.len())]));
+        decision_type.push((next(2) * 2 + next(3) * 4).to_string());
+        left.push(!(leaf as i32));
+        right.push(!(parent.len() as i32));
+        parent[leaf] = Some((node, true));
+        parent.push(Some((node, false)));
+    }
+
+    let ones = |n: usize| join(vec!["1".to_string(); n]);
+    let ints = |v: &[i32]| join(v.iter().map(i32::to_string).collect());
+    format!(
+        "num_leaves={}\nnum_cat=0\nsplit_feature={}\nsplit_gain={}\nthreshold={}\n\
+         decision_type={}\nleft_child={}\nright_child={}\nleaf_value={}\nleaf_weight={}\n\
+         leaf_count={}\ninternal_value={}\ninternal_weight={}\ninternal_count={}\n\
+         is_linear=0\nshrinkage=1\n",
+        num_leaves,
+        join(feature),
+        ones(num_leaves - 1),
+        join(threshold),
+        join(decision_type),
+        ints(&left),
+        ints(&right),
+        join(
+            (0..num_leaves)
+                .map(|i| (i as f64 / 8.0).to_string())
+                .collect()
+        ),
+        ones(num_leaves),
+        ones(num_leaves),
+        ones(num_leaves - 1),
+        ones(num_leaves - 1),
+        ones(num_leaves - 1),
+    )
+}
+
+#[test]
+fn test_batch_predictor_large_trees() {
+    // Trees of more than 64 leaves are traversed, the others scored with bitvectors
+    let large: Vec<String> = [2, 63, 64, 65, 200]
+        .iter()
+        .enumerate()
+        .map(|(seed, &num_leaves)| leaf_wise_tree(num_leaves, seed as u64))
+        .collect();
+    let mut trees: Vec<&str> = large.iter().map(String::as_str).collect();
+    trees.extend_from_slice(&TREES);
+
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+    let text = model_text(&trees, "regression", 1, trees.len(), false);
+    let native = NativeBooster::load_from_string(&text).unwrap();
+    let batch = native.batch_predictor();
+    for predict_type in [predict_type::RAW_SCORE, predict_type::LEAF_INDEX] {
+        let expected = native.predict(&data, num_rows, 3, predict_type).unwrap();
+        let actual = batch.predict(&data, num_rows, 3, predict_type).unwrap();
+        let bits = |values: &[f64]| values.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&actual), bits(&expected));
+    }
+}
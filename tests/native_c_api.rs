@@ -0,0 +1,139 @@
+#![cfg(all(feature = "native", feature = "ffi"))]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{predict_type, Booster, NativeBooster};
+
+const TOLERANCE: f64 = 1e-12;
+
+fn assert_agrees(objective: &str, num_class: usize, average_output: bool) {
+    let text = model_text(&TREES, objective, num_class, 3, average_output);
+    let booster = Booster::load_from_string(&text).unwrap();
+    let native = NativeBooster::load_from_string(&text).unwrap();
+    let data = rows();
+    let num_rows = (data.len() / 3) as i32;
+
+    for predict_type in [
+        predict_type::NORMAL,
+        predict_type::RAW_SCORE,
+        predict_type::LEAF_INDEX,
+    ] {
+        let expected = booster.predict(&data, num_rows, 3, predict_type).unwrap();
+        let actual = native.predict(&data, num_rows, 3, predict_type).unwrap();
+        assert_close(
+            &expected,
+            &actual,
+            &format!("{} type {}", objective, predict_type),
+        );
+    }
+
+    let data_f32: Vec<f32> = data.iter().map(|&x| x as f32).collect();
+    let expected = booster
+        .predict(&data_f32, num_rows, 3, predict_type::RAW_SCORE)
+        .unwrap();
+    let actual = native
+        .predict(&data_f32, num_rows, 3, predict_type::RAW_SCORE)
+        .unwrap();
+    assert_close(&expected, &actual, &format!("{} f32 input", objective));
+}
+
+fn assert_close(expected: &[f64], actual: &[f64], context: &str) {
+    assert_eq!(expected.len(), actual.len(), "{}", context);
+    for (i, (e, a)) in expected.iter().zip(actual).enumerate() {
+        assert!(
+            e == a || (e.is_nan() && a.is_nan()) || (e - a).abs() <= TOLERANCE * e.abs().max(1.0),
+            "{} output {}: C API {} vs native {}",
+            context,
+            i,
+            e,
+            a
+        );
+    }
+}
+
+#[test]
+fn test_regression_objectives() {
+    for objective in [
+        "regression",
+        "regression sqrt",
+        "regression_l1",
+        "huber",
+        "fair",
+        "quantile",
+        "mape",
+        "poisson",
+        "gamma",
+        "tweedie",
+        "lambdarank",
+        "rank_xendcg",
+    ] {
+        assert_agrees(objective, 1, false);
+    }
+}
+
+#[test]
+fn test_classification_objectives() {
+    assert_agrees("binary sigmoid:1", 1, false);
+    assert_agrees("binary sigmoid:0.5", 1, false);
+    assert_agrees("cross_entropy", 1, false);
+    assert_agrees("cross_entropy_lambda", 1, false);
+    assert_agrees("multiclass num_class:3", 3, false);
+    assert_agrees("multiclassova num_class:3 sigmoid:2", 3, false);
+}
+
+#[test]
+fn test_average_output() {
+    assert_agrees("regression", 1, true);
+    assert_agrees("binary sigmoid:1", 1, true);
+}
+
+#[test]
+fn test_contrib() {
+    // SHAP values are not defined for linear trees
+    let trees = &TREES[..3];
+    for (objective, num_class) in [("regression", 1), ("multiclass num_class:3", 3)] {
+        let text = model_text(trees, objective, num_class, 3, false);
+        let booster = Booster::load_from_string(&text).unwrap();
+        let native = NativeBooster::load_from_string(&text).unwrap();
+        let data = rows();
+        let num_rows = (data.len() / 3) as i32;
+
+        let expected = booster
+            .predict(&data, num_rows, 3, predict_type::CONTRIB)
+            .unwrap();
+        let actual = native
+            .predict(&data, num_rows, 3, predict_type::CONTRIB)
+            .unwrap();
+        assert_close(&expected, &actual, &format!("{} contrib", objective));
+    }
+}
+
+#[test]
+fn test_objective_transforms_raw_scores() {
+    for (objective, num_class) in [
+        ("regression sqrt", 1),
+        ("poisson", 1),
+        ("binary sigmoid:0.5", 1),
+        ("cross_entropy_lambda", 1),
+        ("multiclass num_class:3", 3),
+        ("multiclassova num_class:3 sigmoid:2", 3),
+    ] {
+        let text = model_text(&TREES, objective, num_class, 3, false);
+        let booster = Booster::load_from_string(&text).unwrap();
+        let data = rows();
+        let num_rows = (data.len() / 3) as i32;
+
+        let expected = booster
+            .predict(&data, num_rows, 3, predict_type::NORMAL)
+            .unwrap();
+        let mut actual = booster
+            .predict(&data, num_rows, 3, predict_type::RAW_SCORE)
+            .unwrap();
+        let transform = booster.objective().unwrap();
+        transform
+            .transform_predictions(&mut actual, num_class)
+            .unwrap();
+        assert_close(&expected, &actual, objective);
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+
+use lightgbm_rust::objective::Objective;
+
+fn transformed(objective: &str, mut scores: Vec<f64>, num_outputs: usize) -> Vec<f64> {
+    let objective: Objective = objective.parse().unwrap();
+    objective
+        .transform_predictions(&mut scores, num_outputs)
+        .unwrap();
+    scores
+}
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{:?} vs {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn test_parse() {
+    for (header, expected) in [
+        ("regression", Objective::Identity),
+        ("regression sqrt", Objective::Square),
+        ("huber", Objective::Identity),
+        ("quantile", Objective::Identity),
+        ("lambdarank", Objective::Identity),
+        ("tweedie", Objective::Exp),
+        ("binary sigmoid:1", Objective::Sigmoid(1.0)),
+        ("binary", Objective::Sigmoid(1.0)),
+        ("multiclass num_class:3", Objective::Softmax),
+        (
+            "multiclassova num_class:3 sigmoid:2",
+            Objective::SigmoidPerClass(2.0),
+        ),
+        ("cross_entropy", Objective::CrossEntropy),
+        ("cross_entropy_lambda", Objective::CrossEntropyLambda),
+    ] {
+        assert_eq!(header.parse::<Objective>().unwrap(), expected, "{}", header);
+    }
+    assert_eq!(Objective::parse(None).unwrap(), Objective::Identity);
+    assert!("unknown".parse::<Objective>().is_err());
+    assert!("binary sigmoid:x".parse::<Objective>().is_err());
+}
+
+#[test]
+fn test_transforms() {
+    assert_close(&transformed("regression", vec![-2.0, 3.0], 1), &[-2.0, 3.0]);
+    assert_close(
+        &transformed("regression sqrt", vec![-2.0, 3.0], 1),
+        &[-4.0, 9.0],
+    );
+    assert_close(
+        &transformed("poisson", vec![0.0, 1.0], 1),
+        &[1.0, 1.0f64.exp()],
+    );
+    assert_close(
+        &transformed("binary sigmoid:2", vec![0.0, 0.5], 1),
+        &[0.5, 1.0 / (1.0 + (-1.0f64).exp())],
+    );
+    assert_close(
+        &transformed("cross_entropy_lambda", vec![0.0, 1000.0, -1000.0], 1),
+        &[2.0f64.ln(), 1000.0, 0.0],
+    );
+
+    let softmax = transformed(
+        "multiclass num_class:3",
+        vec![0.0, 0.0, 0.0, 1000.0, 0.0, 0.0],
+        3,
+    );
+    assert_close(&softmax, &[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0, 1.0, 0.0, 0.0]);
+
+    let objective: Objective = "multiclass num_class:3".parse().unwrap();
+    assert!(objective.transform_predictions(&mut [0.0; 4], 3).is_err());
+}
@@ -0,0 +1,420 @@
+#![cfg(all(feature = "onnx", feature = "native"))]
+
+mod common;
+
+use common::{grid, model_text, F32_TREES, F32_VALUES};
+use lightgbm_rust::model_format::TextModel;
+use lightgbm_rust::{predict_type, NativeBooster};
+use std::collections::{HashMap, HashSet};
+
+fn read_varint(bytes: &mut &[u8]) -> u64 {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = bytes[0];
+        *bytes = &bytes[1..];
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            break;
+        }
+    }
+    value
+}
+
+/// A protocol buffers value
+enum Value<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+/// Varint and length-delimited values of a protocol buffers field
+fn values(mut bytes: &[u8], field: u64) -> Vec<Value<'_>> {
+    let mut values = Vec::new();
+    while !bytes.is_empty() {
+        let key = read_varint(&mut bytes);
+        let value = match key & 7 {
+            0 => Value::Varint(read_varint(&mut bytes)),
+            2 => {
+                let len = read_varint(&mut bytes) as usize;
+                let (value, rest) = bytes.split_at(len);
+                bytes = rest;
+                Value::Bytes(value)
+            }
+            5 => {
+                bytes = &bytes[4..];
+                continue;
+            }
+            wire_type => panic!("unexpected wire type {}", wire_type),
+        };
+        if key >> 3 == field {
+            values.push(value);
+        }
+    }
+    values
+}
+
+/// Length-delimited values of a protocol buffers field
+fn bytes_fields(bytes: &[u8], field: u64) -> Vec<&[u8]> {
+    values(bytes, field)
+        .into_iter()
+        .filter_map(|value| match value {
+            Value::Bytes(bytes) => Some(bytes),
+            Value::Varint(_) => None,
+        })
+        .collect()
+}
+
+fn varint_field(bytes: &[u8], field: u64) -> Option<u64> {
+    values(bytes, field)
+        .into_iter()
+        .find_map(|value| match value {
+            Value::Varint(value) => Some(value),
+            Value::Bytes(_) => None,
+        })
+}
+
+fn string_field(bytes: &[u8], field: u64) -> String {
+    bytes_fields(bytes, field)
+        .first()
+        .map(|b| String::from_utf8(b.to_vec()).unwrap())
+        .unwrap_or_default()
+}
+
+fn packed_ints(bytes: &[u8], field: u64) -> Vec<i64> {
+    let mut values = Vec::new();
+    for mut packed in bytes_fields(bytes, field) {
+        while !packed.is_empty() {
+            values.push(read_varint(&mut packed) as i64);
+        }
+    }
+    values
+}
+
+fn packed_floats(bytes: &[u8], field: u64) -> Vec<f32> {
+    bytes_fields(bytes, field)
+        .iter()
+        .flat_map(|b| b.chunks_exact(4))
+        .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+        .collect()
+}
+
+/// Attributes of a tree ensemble node
+struct Attributes<'a>(HashMap<String, &'a [u8]>);
+
+impl Attributes<'_> {
+    fn ints(&self, name: &str) -> Vec<i64> {
+        packed_ints(self.0[name], 8)
+    }
+
+    fn floats(&self, name: &str) -> Vec<f32> {
+        packed_floats(self.0[name], 7)
+    }
+
+    fn strings(&self, name: &str) -> Vec<String> {
+        bytes_fields(self.0[name], 9)
+            .iter()
+            .map(|b| String::from_utf8(b.to_vec()).unwrap())
+            .collect()
+    }
+
+    fn int(&self, name: &str) -> i64 {
+        varint_field(self.0[name], 3).unwrap() as i64
+    }
+
+    fn string(&self, name: &str) -> String {
+        string_field(self.0[name], 4)
+    }
+}
+
+/// Declared shape of a graph output, with `None` for the batch dimension
+fn output_shape(graph: &[u8], name: &str) -> Vec<Option<u64>> {
+    let output = bytes_fields(graph, 12)
+        .into_iter()
+        .find(|output| string_field(output, 1) == name)
+        .unwrap_or_else(|| panic!("no output {}", name));
+    let value_type = bytes_fields(output, 2)[0];
+    let tensor_type = bytes_fields(value_type, 1)[0];
+    let shape = bytes_fields(tensor_type, 2)[0];
+    bytes_fields(shape, 1)
+        .iter()
+        .map(|dimension| varint_field(dimension, 1))
+        .collect()
+}
+
+/// The tree ensemble node of an exported model
+struct Ensemble<'a> {
+    graph: &'a [u8],
+    classifier: bool,
+    /// Operators following the ensemble
+    post_ops: Vec<String>,
+    attributes: Attributes<'a>,
+}
+
+impl<'a> Ensemble<'a> {
+    fn parse(model: &'a [u8]) -> Self {
+        let graph = bytes_fields(model, 7)[0];
+        let opsets: Vec<String> = bytes_fields(model, 8)
+            .iter()
+            .map(|opset| string_field(opset, 1))
+            .collect();
+        assert!(opsets.contains(&"ai.onnx.ml".to_string()));
+
+        let nodes = bytes_fields(graph, 1);
+        let op_types: Vec<String> = nodes.iter().map(|node| string_field(node, 4)).collect();
+        let position = op_types
+            .iter()
+            .position(|op| op.starts_with("TreeEnsemble"))
+            .unwrap();
+        let attributes = Attributes(
+            bytes_fields(nodes[position], 5)
+                .into_iter()
+                .map(|attribute| (string_field(attribute, 1), attribute))
+                .collect(),
+        );
+        Ensemble {
+            graph,
+            classifier: op_types[position] == "TreeEnsembleClassifier",
+            post_ops: op_types[position + 1..].to_vec(),
+            attributes,
+        }
+    }
+
+    fn leaf_attribute(&self, name: &str) -> String {
+        let prefix = if self.classifier { "class" } else { "target" };
+        format!("{}_{}", prefix, name)
+    }
+
+    /// Width of the output: the number of classes or targets
+    fn num_outputs(&self) -> usize {
+        if self.classifier {
+            self.attributes.ints("classlabels_int64s").len()
+        } else {
+            self.attributes.int("n_targets") as usize
+        }
+    }
+
+    /// Index of the first node of each tree, after checking that each tree is a contiguous
+    /// run of nodes whose first node is its root
+    fn roots(&self) -> Vec<usize> {
+        let tree_ids = self.attributes.ints("nodes_treeids");
+        let node_ids = self.attributes.ints("nodes_nodeids");
+        let modes = self.attributes.strings("nodes_modes");
+        let true_ids = self.attributes.ints("nodes_truenodeids");
+        let false_ids = self.attributes.ints("nodes_falsenodeids");
+        let leaves: HashSet<(i64, i64)> = self
+            .attributes
+            .ints(&self.leaf_attribute("treeids"))
+            .into_iter()
+            .zip(self.attributes.ints(&self.leaf_attribute("nodeids")))
+            .collect();
+
+        let mut roots = Vec::new();
+        for (i, &tree) in tree_ids.iter().enumerate() {
+            if i == 0 || tree_ids[i - 1] != tree {
+                assert!(
+                    roots.iter().all(|&root| tree_ids[root] != tree),
+                    "nodes of tree {} are not contiguous",
+                    tree
+                );
+                roots.push(i);
+            }
+        }
+        for (r, &root) in roots.iter().enumerate() {
+            let end = roots.get(r + 1).copied().unwrap_or(tree_ids.len());
+            let tree = tree_ids[root];
+            let index: HashMap<i64, usize> = (root..end).map(|i| (node_ids[i], i)).collect();
+            assert_eq!(index.len(), end - root, "tree {} repeats node ids", tree);
+
+            // Walk the tree from its first node: every node must be reached, and the first
+            // node must not be the child of another
+            let mut reached = vec![false; end - root];
+            let mut stack = vec![root];
+            while let Some(i) = stack.pop() {
+                if std::mem::replace(&mut reached[i - root], true) {
+                    continue;
+                }
+                if modes[i] == "LEAF" {
+                    assert!(leaves.contains(&(tree, node_ids[i])), "tree {} leaf", tree);
+                    continue;
+                }
+                for child in [true_ids[i], false_ids[i]] {
+                    assert_ne!(child, node_ids[root], "tree {} root has a parent", tree);
+                    stack.push(index[&child]);
+                }
+            }
+            assert!(
+                reached.iter().all(|&r| r),
+                "tree {} has unreachable nodes",
+                tree
+            );
+        }
+        roots
+    }
+}
+
+/// Evaluate an exported model the way ONNX Runtime does, in `f64` from the `f32` weights,
+/// returning the declared final output of each row
+fn evaluate(model: &[u8], data: &[f32], num_features: usize) -> Vec<f64> {
+    let ensemble = Ensemble::parse(model);
+    let attributes = &ensemble.attributes;
+    let initializers: HashMap<String, Vec<f32>> = bytes_fields(ensemble.graph, 5)
+        .iter()
+        .map(|tensor| (string_field(tensor, 8), packed_floats(tensor, 4)))
+        .collect();
+    let zero_value = &initializers["zero_value"];
+    assert_eq!(initializers["zero_threshold"], [1e-35f32]);
+
+    let tree_ids = attributes.ints("nodes_treeids");
+    let node_ids = attributes.ints("nodes_nodeids");
+    let features = attributes.ints("nodes_featureids");
+    let modes = attributes.strings("nodes_modes");
+    let values = attributes.floats("nodes_values");
+    let true_ids = attributes.ints("nodes_truenodeids");
+    let false_ids = attributes.ints("nodes_falsenodeids");
+    let tracks_true = attributes.ints("nodes_missing_value_tracks_true");
+    let leaf_trees = attributes.ints(&ensemble.leaf_attribute("treeids"));
+    let leaf_nodes = attributes.ints(&ensemble.leaf_attribute("nodeids"));
+    let leaf_targets = attributes.ints(&ensemble.leaf_attribute("ids"));
+    let leaf_weights = attributes.floats(&ensemble.leaf_attribute("weights"));
+    let post_transform = attributes.string("post_transform");
+
+    let index: HashMap<(i64, i64), usize> = tree_ids
+        .iter()
+        .zip(&node_ids)
+        .enumerate()
+        .map(|(i, (&t, &n))| ((t, n), i))
+        .collect();
+    let roots = ensemble.roots();
+    let num_outputs = ensemble.num_outputs();
+    // A binary classifier scores only its second class; ONNX Runtime fills in the first
+    let binary = ensemble.classifier && num_outputs == 2 && leaf_targets.iter().all(|&t| t == 0);
+
+    let mut output = Vec::new();
+    for row in data.chunks_exact(num_features) {
+        let row: Vec<f32> = row
+            .iter()
+            .zip(zero_value)
+            .map(|(&x, &zero)| if x.abs() <= 1e-35 { zero } else { x })
+            .collect();
+        let mut scores = vec![0.0f64; num_outputs];
+        for &root in &roots {
+            let tree = tree_ids[root];
+            let mut i = root;
+            while modes[i] != "LEAF" {
+                let x = row[features[i] as usize];
+                let condition = match modes[i].as_str() {
+                    "BRANCH_LEQ" => x <= values[i],
+                    "BRANCH_EQ" => x == values[i],
+                    mode => panic!("unexpected mode {}", mode),
+                };
+                let next = if condition || (x.is_nan() && tracks_true[i] != 0) {
+                    true_ids[i]
+                } else {
+                    false_ids[i]
+                };
+                i = index[&(tree, next)];
+            }
+            for (l, (&t, &n)) in leaf_trees.iter().zip(&leaf_nodes).enumerate() {
+                if t == tree && n == node_ids[i] {
+                    scores[leaf_targets[l] as usize] += f64::from(leaf_weights[l]);
+                }
+            }
+        }
+
+        match post_transform.as_str() {
+            "LOGISTIC" if binary => {
+                let p = 1.0 / (1.0 + (-scores[0]).exp());
+                scores = vec![1.0 - p, p];
+            }
+            "LOGISTIC" => scores
+                .iter_mut()
+                .for_each(|s| *s = 1.0 / (1.0 + (-*s).exp())),
+            "SOFTMAX" => {
+                let sum: f64 = scores.iter().map(|s| s.exp()).sum();
+                scores.iter_mut().for_each(|s| *s = s.exp() / sum);
+            }
+            "NONE" => {}
+            transform => panic!("unexpected post transform {}", transform),
+        }
+        for op in &ensemble.post_ops {
+            match op.as_str() {
+                "Exp" => scores.iter_mut().for_each(|s| *s = s.exp()),
+                "Sigmoid" => scores
+                    .iter_mut()
+                    .for_each(|s| *s = 1.0 / (1.0 + (-*s).exp())),
+                "Softplus" => scores.iter_mut().for_each(|s| *s = s.exp().ln_1p()),
+                "Abs" => {}
+                "Mul" => scores.iter_mut().for_each(|s| *s *= s.abs()),
+                op => panic!("unexpected op {}", op),
+            }
+        }
+        output.extend(scores);
+    }
+    output
+}
+
+#[test]
+fn test_onnx_matches_native() {
+    let data = grid(&F32_VALUES);
+    let num_rows = (data.len() / 3) as i32;
+    for (objective, num_class, average_output) in [
+        ("regression", 1, false),
+        ("regression", 1, true),
+        ("regression sqrt", 1, false),
+        ("poisson", 1, false),
+        ("cross_entropy", 1, false),
+        ("cross_entropy_lambda", 1, false),
+        ("binary sigmoid:0.5", 1, false),
+        ("multiclass num_class:3", 3, false),
+        ("multiclassova num_class:3 sigmoid:2", 3, false),
+    ] {
+        let text = model_text(&F32_TREES, objective, num_class, 3, average_output);
+        let native = NativeBooster::load_from_string(&text).unwrap();
+        let onnx = TextModel::parse(&text).unwrap().to_onnx().unwrap();
+
+        let ensemble = Ensemble::parse(&onnx);
+        assert_eq!(ensemble.roots().len(), 3 * num_class, "{}", objective);
+        let width = ensemble.num_outputs();
+        if ensemble.classifier {
+            assert_eq!(output_shape(ensemble.graph, "label"), [None]);
+        }
+        let output = if ensemble.classifier {
+            "probabilities"
+        } else {
+            "variable"
+        };
+        assert_eq!(
+            output_shape(ensemble.graph, output),
+            [None, Some(width as u64)],
+            "{}",
+            objective
+        );
+
+        let mut expected = native
+            .predict(&data, num_rows, 3, predict_type::NORMAL)
+            .unwrap();
+        if width != num_class {
+            // The binary classifier outputs the probabilities of both classes
+            assert_eq!((width, num_class), (2, 1), "{}", objective);
+            expected = expected.iter().flat_map(|&p| [1.0 - p, p]).collect();
+        }
+        let actual = evaluate(&onnx, &data, 3);
+        assert_eq!(expected.len(), actual.len(), "{}", objective);
+        for (i, (e, a)) in expected.iter().zip(&actual).enumerate() {
+            assert!(
+                (e - a).abs() <= 1e-6 * e.abs().max(1.0),
+                "{} output {}: native {} vs ONNX {}",
+                objective,
+                i,
+                e,
+                a
+            );
+        }
+    }
+}
+
+#[test]
+fn test_onnx_rejects_linear_trees() {
+    let mut model = TextModel::parse(&model_text(&F32_TREES, "regression", 1, 3, false)).unwrap();
+    model.trees[1].is_linear = true;
+    assert!(model.to_onnx().is_err());
+}
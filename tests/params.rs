@@ -0,0 +1,86 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{model_text, TREES};
+use lightgbm_rust::{Booster, ModelParams};
+use serde_json::json;
+
+const PARAMETERS: &str = "
+parameters:
+[boosting: gbdt]
+[objective: binary]
+[data_sample_strategy: bagging]
+[metric: auc,binary_logloss]
+[num_iterations: 100]
+[learning_rate: 0.05]
+[num_leaves: 31]
+[max_depth: -1]
+[lambda_l2: 0.5]
+[sigmoid: 1]
+[num_class: 1]
+[verbosity: -1]
+[linear_tree: 0]
+[zero_as_missing: 1]
+[categorical_feature: 0,1]
+end of parameters
+";
+
+#[test]
+fn test_params_of_loaded_model() {
+    let text = model_text(&TREES, "binary sigmoid:1", 1, 1, false) + PARAMETERS;
+    let params = Booster::load_from_string(&text).unwrap().params().unwrap();
+
+    assert_eq!(params.boosting.as_deref(), Some("gbdt"));
+    assert_eq!(params.objective.as_deref(), Some("binary"));
+    assert_eq!(params.metric, ["auc", "binary_logloss"]);
+    assert_eq!(params.num_iterations, Some(100));
+    assert_eq!(params.learning_rate, Some(0.05));
+    assert_eq!(params.num_leaves, Some(31));
+    assert_eq!(params.max_depth, Some(-1));
+    assert_eq!(params.lambda_l2, Some(0.5));
+    assert_eq!(params.sigmoid, Some(1.0));
+    assert_eq!(params.num_class, Some(1));
+    assert_eq!(params.linear_tree, Some(false));
+    assert_eq!(params.zero_as_missing, Some(true));
+    assert_eq!(params.categorical_feature.as_deref(), Some("0,1"));
+
+    // Parameters without a field are still available
+    assert_eq!(params.get("verbosity"), Some(&json!(-1)));
+    assert_eq!(params.get("data_sample_strategy"), Some(&json!("bagging")));
+    assert_eq!(params.get("not_a_parameter"), None);
+    assert_eq!(params.raw().len(), 15);
+}
+
+#[test]
+fn test_params_without_parameters_section() {
+    let text = model_text(&TREES, "regression", 1, 1, false);
+    let params = Booster::load_from_string(&text).unwrap().params().unwrap();
+    assert_eq!(params, ModelParams::default());
+    assert!(params.raw().is_empty());
+}
+
+#[test]
+fn test_params_from_json() {
+    let params = ModelParams::from_json(
+        r#"{"objective": "multiclass", "num_class": 3, "feature_fraction": 0.8, "max_bin": 255}"#,
+    )
+    .unwrap();
+    assert_eq!(params.objective.as_deref(), Some("multiclass"));
+    assert_eq!(params.num_class, Some(3));
+    assert_eq!(params.feature_fraction, Some(0.8));
+    assert_eq!(params.learning_rate, None);
+    assert!(params.metric.is_empty());
+    assert_eq!(params.get("max_bin"), Some(&json!(255)));
+
+    for invalid in [
+        r#"{"num_leaves": "many"}"#,
+        r#"{"metric": 1}"#,
+        r#"{"linear_tree": 2}"#,
+        "[]",
+        "{",
+        "",
+    ] {
+        assert!(ModelParams::from_json(invalid).is_err(), "{}", invalid);
+    }
+}
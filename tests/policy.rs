@@ -0,0 +1,35 @@
+#![cfg(feature = "ffi")]
+
+use lightgbm_rust::{Adjustment, InfinityPolicy, InputPolicy};
+
+#[test]
+fn test_apply_adjusts_values() {
+    let policy = InputPolicy::new()
+        .with_sentinel(1, -999.0)
+        .with_infinity(InfinityPolicy::Clamp);
+    let data = [1.0, -999.0, -999.0, f64::NEG_INFINITY];
+    let (cleaned, report) = policy.apply(&data, 2).unwrap();
+
+    assert_eq!(cleaned[0], 1.0);
+    assert!(cleaned[1].is_nan());
+    assert_eq!(cleaned[2], -999.0);
+    assert_eq!(cleaned[3], f64::MIN);
+    assert_eq!(report.rows(), [0, 1]);
+    assert_eq!(report.columns(), [1]);
+    assert_eq!(report.adjusted[1].adjustment, Adjustment::InfinityClamped);
+}
+
+#[test]
+fn test_apply_rejects_invalid_shape() {
+    let policy = InputPolicy::new().with_sentinel(0, -1.0);
+    assert!(policy.apply(&[1.0, 2.0], 0).is_err());
+    assert!(policy.apply(&[1.0, 2.0, 3.0], 2).is_err());
+    assert!(policy.apply::<f64>(&[], 0).is_err());
+    assert!(InputPolicy::new()
+        .with_sentinel(2, -1.0)
+        .apply(&[1.0, 2.0], 2)
+        .is_err());
+
+    let (cleaned, report) = policy.apply::<f64>(&[], usize::MAX).unwrap();
+    assert!(cleaned.is_empty() && report.is_empty());
+}
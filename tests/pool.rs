@@ -0,0 +1,109 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use common::{model_text, rows, TREES};
+use lightgbm_rust::{predict_type, Booster, BoosterPool};
+use std::thread;
+
+fn model() -> String {
+    model_text(&TREES, "multiclass num_class:3", 3, 3, false)
+}
+
+/// Compare bit patterns, as some outputs are NaN
+fn assert_same(actual: &[f64], expected: &[f64]) {
+    let bits = |values: &[f64]| values.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    assert_eq!(bits(actual), bits(expected));
+}
+
+#[test]
+fn test_pool_matches_predict() {
+    let text = model();
+    let booster = Booster::load_from_string(&text).unwrap();
+    let data = rows();
+    let data_f32: Vec<f32> = data.iter().map(|&x| x as f32).collect();
+    let num_rows = (data.len() / 3) as i32;
+
+    for num_boosters in [1, 3] {
+        for chunk_size in [1, 7, num_rows as usize, 10_000] {
+            let pool = BoosterPool::load_from_string(&text, num_boosters)
+                .unwrap()
+                .with_chunk_size(chunk_size)
+                .with_num_threads(2);
+            assert_eq!(pool.num_boosters(), num_boosters);
+
+            for predict_type in [predict_type::NORMAL, predict_type::LEAF_INDEX] {
+                let expected = booster.predict(&data, num_rows, 3, predict_type).unwrap();
+                let actual = pool.predict(&data, num_rows, 3, predict_type).unwrap();
+                assert_same(&actual, &expected);
+            }
+
+            let expected = booster
+                .predict(&data_f32, num_rows, 3, predict_type::RAW_SCORE)
+                .unwrap();
+            let actual = pool
+                .predict(&data_f32, num_rows, 3, predict_type::RAW_SCORE)
+                .unwrap();
+            assert_same(&actual, &expected);
+        }
+    }
+}
+
+#[test]
+fn test_pool_shared_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<BoosterPool>();
+
+    let text = model();
+    let booster = Booster::load_from_string(&text).unwrap();
+    let data = rows();
+    let num_rows = data.len() / 3;
+
+    // Each thread predicts its own slice of rows, so that results mixed up between
+    // threads or chunks would not match
+    let inputs: Vec<&[f64]> = (0..8)
+        .map(|i| &data[i * 3 * (num_rows / 8)..(i + 1) * 3 * (num_rows / 8)])
+        .collect();
+    let expected: Vec<Vec<f64>> = inputs
+        .iter()
+        .map(|input| {
+            booster
+                .predict(input, (input.len() / 3) as i32, 3, predict_type::NORMAL)
+                .unwrap()
+        })
+        .collect();
+
+    let pool = BoosterPool::load_from_string(&text, 2)
+        .unwrap()
+        .with_chunk_size(16);
+    thread::scope(|scope| {
+        for (input, expected) in inputs.iter().zip(&expected) {
+            let pool = &pool;
+            scope.spawn(move || {
+                for _ in 0..20 {
+                    let actual = pool
+                        .predict(input, (input.len() / 3) as i32, 3, predict_type::NORMAL)
+                        .unwrap();
+                    assert_same(&actual, expected);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn test_pool_errors() {
+    let text = model();
+    assert!(BoosterPool::load_from_string(&text, 0).is_err());
+    assert!(BoosterPool::load_from_string("not a model", 2).is_err());
+
+    let pool = BoosterPool::load_from_string(&text, 2).unwrap();
+    let err = pool
+        .predict(&[1.0, 2.0, 3.0, 4.0], 2, 3, predict_type::NORMAL)
+        .unwrap_err();
+    assert!(
+        err.description.contains("size mismatch"),
+        "{}",
+        err.description
+    );
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "ffi")]
+
+use lightgbm_rust::{predict_type, Booster};
+
+/// A single stump: `x <= 0.5` scores -1, otherwise 2
+const MODEL: &str = "tree
+version=v4
+num_class=1
+num_tree_per_iteration=1
+label_index=0
+max_feature_idx=1
+objective=regression
+feature_names=a b
+feature_infos=[-1:3] [-1:3]
+tree_sizes=243
+
+Tree=0
+num_leaves=2
+num_cat=0
+split_feature=0
+split_gain=1
+threshold=0.5
+decision_type=2
+left_child=-1
+right_child=-2
+leaf_value=-1 2
+leaf_weight=1 1
+leaf_count=1 1
+internal_value=0
+internal_weight=0
+internal_count=2
+is_linear=0
+shrinkage=1
+
+
+end of trees
+";
+
+#[test]
+fn test_predict_iter_chunks() {
+    let booster = Booster::load_from_string(MODEL).unwrap();
+    let rows: Vec<[f64; 2]> = (0..10).map(|i| [i as f64 * 0.25, 0.0]).collect();
+    let expected = booster
+        .predict(rows.concat().as_slice(), 10, 2, predict_type::NORMAL)
+        .unwrap();
+
+    for chunk_size in [1, 3, 10, 64] {
+        let predictions: Vec<f64> = booster
+            .predict_iter(&rows, 2, predict_type::NORMAL, chunk_size)
+            .map(|p| p.unwrap()[0])
+            .collect();
+        assert_eq!(predictions, expected, "chunk size {}", chunk_size);
+    }
+}
+
+#[test]
+fn test_predict_iter_yields_rows_before_invalid_row() {
+    let booster = Booster::load_from_string(MODEL).unwrap();
+    let rows = vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![1.0], vec![0.0, 0.0]];
+
+    let results: Vec<_> = booster
+        .predict_iter(&rows, 2, predict_type::NORMAL, 3)
+        .collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &[-1.0]);
+    assert_eq!(results[1].as_ref().unwrap(), &[2.0]);
+    assert!(results[2].is_err());
+}